@@ -1,40 +1,191 @@
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput,
+    Fields, Generics, Member,
+};
 
-#[proc_macro_derive(Component)]
+/// Components can be structs and enums with unit variants only.
+/// `#[component(reflect)]` additionally implements `Reflect` with the fields metadata
+#[proc_macro_derive(Component, attributes(component))]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let is_supported = match input.data {
-        syn::Data::Struct(s) => s.fields.len() > 0,
-        _ => false,
+    expand_component(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Tags can be structs without fields or with `PhantomData` fields only.
+/// `#[tag(archetype)]` additionally implements `Component` to store the tag
+/// as the zero-sized archetype member
+#[proc_macro_derive(Tag, attributes(tag))]
+pub fn derive_tag(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_tag(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_component(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    match &input.data {
+        Data::Struct(_) => {}
+        Data::Enum(data) => {
+            if let Some(variant) = data.variants.iter().find(|v| !v.fields.is_empty()) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "Component enums can have unit variants only",
+                ));
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "Components can be structs or enums only",
+            ))
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut expanded = quote! {
+        impl #impl_generics ::division_ecs::Component for #ident #ty_generics #where_clause {}
     };
 
-    if !is_supported {
-        panic!("Components can be non-empty structs only");
+    if let Some(reflect_attr) = find_reflect_attr(input)? {
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                reflect_attr,
+                "Only struct components can be reflected",
+            ));
+        };
+
+        expanded.extend(expand_reflect(input, &data.fields));
     }
 
-    let type_name = input.ident.to_string();
-    format!("impl Component for {type_name} {{}}")
-        .parse()
-        .unwrap()
+    Ok(expanded)
 }
 
-#[proc_macro_derive(Tag)]
-pub fn derive_tag(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+fn expand_reflect(input: &DeriveInput, fields: &Fields) -> TokenStream2 {
+    let field_infos = fields.iter().zip(fields.members()).map(|(field, member)| {
+        let name = match &member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+
+        quote::quote_spanned! {field.span()=>
+            Self::reflect_field(
+                #name,
+                ::core::mem::offset_of!(Self, #member),
+                |component| &component.#member,
+            )
+        }
+    });
+
+    let ident = &input.ident;
+    let generics = with_static_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let is_supported = match input.data {
-        syn::Data::Struct(s) => s.fields.len() == 0,
-        _ => false
+    quote! {
+        unsafe impl #impl_generics ::division_ecs::Reflect for #ident #ty_generics #where_clause {
+            fn reflect_fields() -> ::std::vec::Vec<::division_ecs::FieldInfo> {
+                ::std::vec![#(#field_infos),*]
+            }
+        }
+    }
+}
+
+fn expand_tag(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Tags can be structs only",
+        ));
     };
 
-    if !is_supported {
-        panic!("Tags can be empty structs only");
+    if let Some(field) = data.fields.iter().find(|f| !is_phantom_data(&f.ty)) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "Tags can have `PhantomData` fields only",
+        ));
+    }
+
+    let ident = &input.ident;
+    let generics = with_static_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if !has_archetype_tag_attr(input)? {
+        return Ok(quote! {
+            impl #impl_generics ::division_ecs::Tag for #ident #ty_generics #where_clause {}
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::division_ecs::Component for #ident #ty_generics #where_clause {}
+
+        impl #impl_generics ::division_ecs::Tag for #ident #ty_generics #where_clause {
+            const IS_ARCHETYPE_TAG: bool = true;
+        }
+    })
+}
+
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// `#[component(reflect)]` attribute, if the input has it
+fn find_reflect_attr(input: &DeriveInput) -> syn::Result<Option<&syn::Attribute>> {
+    let mut reflect_attr = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("component"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("reflect") {
+                reflect_attr = Some(attr);
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported component attribute"))
+            }
+        })?;
+    }
+
+    Ok(reflect_attr)
+}
+
+/// Whether the input has the `#[tag(archetype)]` attribute
+fn has_archetype_tag_attr(input: &DeriveInput) -> syn::Result<bool> {
+    let mut is_archetype = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("tag")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("archetype") {
+                is_archetype = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported tag attribute"))
+            }
+        })?;
+    }
+
+    Ok(is_archetype)
+}
+
+/// Traits with the `'static` supertrait need all type parameters to be `'static`
+fn with_static_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!('static));
     }
 
-    let type_name = input.ident.to_string();
-    format!("impl Tag for {type_name} {{}}")
-        .parse()
-        .unwrap()
+    generics
 }
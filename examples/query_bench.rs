@@ -66,6 +66,21 @@ fn main() {
 
     println!("Query with tags: {result}. With time: {delta_time:?}");
 
+    let mut query = query::component::read_write::<TestComponent2, TestComponent1>();
+    let begin = Instant::now();
+
+    store
+        .component_query_iter(&mut query)
+        .par_for_each(|(comp2, comp1)| {
+            comp1.x += comp2.xx as f32;
+            comp1.y += comp2.yy;
+            comp1.z += comp2.zz as f32;
+        });
+
+    let delta_time = Instant::now() - begin;
+
+    println!("Parallel write query with time: {delta_time:?}");
+
     let mut query =
         query::ordered_component::readonly::<TestTag, (TestComponent1, TestComponent2)>();
 
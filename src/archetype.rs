@@ -1,17 +1,22 @@
 use std::{
-    any::TypeId,
     fmt::{Debug, Display},
     hash::{Hash, Hasher},
 };
 
-use crate::{component_tuple::ComponentTuple, component_type::ComponentType, mem_utils};
+use crate::{
+    component_tuple::ComponentTuple,
+    component_type::{ComponentCloneFn, ComponentDropFn, ComponentId, ComponentType},
+    mem_utils,
+};
 
 #[derive(Debug)]
 pub struct Archetype {
-    ids: *mut TypeId,
+    ids: *mut ComponentId,
     sizes: *mut usize,
     aligns: *mut usize,
     names: *mut &'static str,
+    drop_fns: *mut Option<ComponentDropFn>,
+    clone_fns: *mut Option<ComponentCloneFn>,
     component_count: usize,
 }
 
@@ -29,17 +34,21 @@ impl Archetype {
         let component_count = sorted_components.len();
         assert!(component_count > 0);
 
-        let (ids, sizes, aligns, names): (
-            *mut TypeId,
+        let (ids, sizes, aligns, names, drop_fns, clone_fns): (
+            *mut ComponentId,
             *mut usize,
             *mut usize,
             *mut &'static str,
+            *mut Option<ComponentDropFn>,
+            *mut Option<ComponentCloneFn>,
         ) = unsafe {
             (
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
+                mem_utils::alloc(component_count),
+                mem_utils::alloc(component_count),
             )
         };
 
@@ -49,6 +58,8 @@ impl Archetype {
                 *sizes.add(i) = comp.size();
                 *aligns.add(i) = comp.align();
                 *names.add(i) = comp.name();
+                *drop_fns.add(i) = comp.drop_fn();
+                *clone_fns.add(i) = comp.clone_fn();
             };
         }
 
@@ -57,13 +68,15 @@ impl Archetype {
             sizes,
             aligns,
             names,
+            drop_fns,
+            clone_fns,
             component_count,
         }
     }
 
     #[inline(always)]
     pub fn has_component<T: 'static>(&self) -> bool {
-        self.find_component_index(TypeId::of::<T>()).is_some()
+        self.find_component_index(ComponentId::of::<T>()).is_some()
     }
 
     #[inline(always)]
@@ -74,15 +87,17 @@ impl Archetype {
                 *self.sizes.add(i),
                 *self.aligns.add(i),
                 *self.names.add(i),
+                *self.drop_fns.add(i),
+                *self.clone_fns.add(i),
             )
         })
     }
 
     pub fn find_component_index_of<T: 'static>(&self) -> Option<usize> {
-        self.find_component_index(std::any::TypeId::of::<T>())
+        self.find_component_index(ComponentId::of::<T>())
     }
 
-    pub fn find_component_index(&self, type_id: TypeId) -> Option<usize> {
+    pub fn find_component_index(&self, type_id: ComponentId) -> Option<usize> {
         unsafe {
             let slice = &*std::ptr::slice_from_raw_parts(self.ids, self.component_count);
             match slice.binary_search(&type_id) {
@@ -107,6 +122,20 @@ impl Archetype {
         self.sizes
     }
 
+    #[inline]
+    pub(crate) fn component_drop_fns(&self) -> *const Option<ComponentDropFn> {
+        self.drop_fns
+    }
+
+    /// Returns true if at least one of the archetype components needs drop
+    pub fn needs_drop(&self) -> bool {
+        unsafe {
+            std::slice::from_raw_parts(self.drop_fns, self.component_count)
+                .iter()
+                .any(|d| d.is_some())
+        }
+    }
+
     pub fn is_same_as(&self, other: &Self) -> bool {
         if self.component_count != other.component_count {
             return false;
@@ -124,7 +153,7 @@ impl Archetype {
         self.is_include_ids(other.included_ids())
     }
 
-    pub fn is_include_ids(&self, ids_to_check: &[TypeId]) -> bool {
+    pub fn is_include_ids(&self, ids_to_check: &[ComponentId]) -> bool {
         let self_ids = self.included_ids();
 
         if self_ids.len() < ids_to_check.len() {
@@ -141,7 +170,7 @@ impl Archetype {
         return true;
     }
 
-    pub fn is_include_only_ids(&self, ids_to_check: &[TypeId]) -> bool {
+    pub fn is_include_only_ids(&self, ids_to_check: &[ComponentId]) -> bool {
         let self_ids = self.included_ids();
 
         if self_ids.len() != ids_to_check.len() {
@@ -163,7 +192,7 @@ impl Archetype {
         self.is_exclude_ids(other.included_ids())
     }
 
-    pub fn is_exclude_ids(&self, ids_to_check: &[TypeId]) -> bool {
+    pub fn is_exclude_ids(&self, ids_to_check: &[ComponentId]) -> bool {
         let self_ids = self.included_ids();
 
         let mut left_idx = 0;
@@ -185,7 +214,7 @@ impl Archetype {
     }
 
     #[inline(always)]
-    pub fn included_ids(&self) -> &[TypeId] {
+    pub fn included_ids(&self) -> &[ComponentId] {
         unsafe { &*std::ptr::slice_from_raw_parts(self.ids, self.component_count) }
     }
 }
@@ -241,12 +270,14 @@ impl ArchetypesUnion {
 impl Clone for Archetype {
     fn clone(&self) -> Self {
         let component_count = self.component_count;
-        let (ids, sizes, aligns, names) = unsafe {
+        let (ids, sizes, aligns, names, drop_fns, clone_fns) = unsafe {
             (
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
                 mem_utils::alloc(component_count),
+                mem_utils::alloc(component_count),
+                mem_utils::alloc(component_count),
             )
         };
 
@@ -254,7 +285,11 @@ impl Clone for Archetype {
             self.ids.copy_to_nonoverlapping(ids, component_count);
             self.sizes.copy_to_nonoverlapping(sizes, component_count);
             self.aligns.copy_to_nonoverlapping(aligns, component_count);
-            self.names.copy_to_nonoverlapping(names, component_count)
+            self.names.copy_to_nonoverlapping(names, component_count);
+            self.drop_fns
+                .copy_to_nonoverlapping(drop_fns, component_count);
+            self.clone_fns
+                .copy_to_nonoverlapping(clone_fns, component_count);
         }
 
         Self {
@@ -262,6 +297,8 @@ impl Clone for Archetype {
             sizes,
             aligns,
             names,
+            drop_fns,
+            clone_fns,
             component_count,
         }
     }
@@ -294,6 +331,8 @@ impl Drop for Archetype {
             mem_utils::dealloc(self.sizes, self.component_count);
             mem_utils::dealloc(self.aligns, self.component_count);
             mem_utils::dealloc(self.names, self.component_count);
+            mem_utils::dealloc(self.drop_fns, self.component_count);
+            mem_utils::dealloc(self.clone_fns, self.component_count);
         }
     }
 }
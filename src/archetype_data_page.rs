@@ -1,11 +1,28 @@
-use crate::{archetype::ArchetypesUnion, mem_utils, Archetype, archetype_layout::ArchetypeLayout};
+use std::sync::atomic::{AtomicU32, Ordering};
 
-/// Reusable page of the components data with a fixed size (4096 Bytes), related to the concrete archetype.
+use crate::{
+    archetype::ArchetypesUnion,
+    archetype_data_page_view::ArchetypeDataPageView,
+    archetype_layout::ArchetypeLayout,
+    component_type::{ComponentCloneFn, ComponentId},
+    mem_utils, tick_utils, Archetype,
+};
+
+/// Reusable page of the components data (16 KiB by default), related to the concrete archetype.
 /// It contains data for all components of the some entities subset
 #[derive(Debug)]
 pub struct ArchetypeDataPage {
     entities_ids: Vec<u32>,
+    entity_capacity: usize,
     components_data_ptr: *mut u8,
+    size_bytes: usize,
+
+    /// Last change tick of the every component column in the page. Columns are
+    /// marked through the shared page by the systems running in parallel
+    component_changed_ticks: Vec<AtomicU32>,
+    /// Tick, when the component was added to the entity. Stored by the columns
+    /// of `entity_capacity` length
+    entity_added_ticks: Vec<AtomicU32>,
 }
 
 pub(crate) struct SwapRemoveInfo {
@@ -15,18 +32,100 @@ pub(crate) struct SwapRemoveInfo {
 impl ArchetypeDataPage {
     pub const PAGE_SIZE_BYTES: usize = 4096 * 4;
 
-    pub(crate) fn new() -> Self {
-        let components_data_ptr = unsafe { mem_utils::alloc(Self::PAGE_SIZE_BYTES) };
+    pub(crate) fn new(size_bytes: usize) -> Self {
+        let components_data_ptr = unsafe { mem_utils::alloc(size_bytes) };
 
         ArchetypeDataPage {
             components_data_ptr,
+            size_bytes,
             entities_ids: Vec::new(),
+            entity_capacity: 0,
+            component_changed_ticks: Vec::new(),
+            entity_added_ticks: Vec::new(),
         }
     }
 
     pub(crate) fn set_layout(&mut self, archetype: &ArchetypeLayout) {
+        let size_bytes = archetype.page_size();
+        if size_bytes != self.size_bytes {
+            unsafe {
+                mem_utils::dealloc(self.components_data_ptr, self.size_bytes);
+                self.components_data_ptr = mem_utils::alloc(size_bytes);
+            }
+            self.size_bytes = size_bytes;
+        }
+
         let capacity = archetype.entities_capacity();
+        self.entity_capacity = capacity;
         self.entities_ids.reserve(capacity);
+
+        let component_count = archetype.component_count();
+        self.component_changed_ticks.clear();
+        self.component_changed_ticks
+            .resize_with(component_count, Default::default);
+
+        self.entity_added_ticks.clear();
+        self.entity_added_ticks
+            .resize_with(component_count * self.entity_capacity(), Default::default);
+    }
+
+    #[inline(always)]
+    pub(crate) fn component_changed_tick(&self, component_index: usize) -> u32 {
+        self.component_changed_ticks[component_index].load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub(crate) fn mark_component_changed(&self, component_index: usize, tick: u32) {
+        self.component_changed_ticks[component_index].store(tick, Ordering::Relaxed);
+    }
+
+    /// Marks components with the ids as changed. Ids, which the page archetype doesn't
+    /// contain, are skipped
+    pub(crate) fn mark_components_changed(
+        &self,
+        archetype: &Archetype,
+        type_ids: &[ComponentId],
+        tick: u32
+    ) {
+        for id in type_ids {
+            if let Some(idx) = archetype.find_component_index(*id) {
+                self.mark_component_changed(idx, tick);
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn component_added_tick(
+        &self,
+        component_index: usize,
+        entity_index: usize
+    ) -> u32 {
+        self.entity_added_ticks[self.added_tick_index(component_index, entity_index)]
+            .load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_component_added_tick(
+        &self,
+        component_index: usize,
+        entity_index: usize,
+        tick: u32
+    ) {
+        self.entity_added_ticks[self.added_tick_index(component_index, entity_index)]
+            .store(tick, Ordering::Relaxed);
+    }
+
+    /// Marks all components of the entity as added and changed
+    pub(crate) fn mark_entity_added(&self, entity_index: usize, tick: u32) {
+        for component_index in 0..self.component_changed_ticks.len() {
+            self.set_component_added_tick(component_index, entity_index, tick);
+            self.mark_component_changed(component_index, tick);
+        }
+    }
+
+    #[inline(always)]
+    fn added_tick_index(&self, component_index: usize, entity_index: usize) -> usize {
+        component_index * self.entity_capacity() + entity_index
     }
 
     #[inline(always)]
@@ -36,7 +135,7 @@ impl ArchetypeDataPage {
 
     #[inline(always)]
     pub(crate) fn entity_capacity(&self) -> usize {
-        self.entities_ids.capacity()
+        self.entity_capacity
     }
 
     #[inline(always)]
@@ -55,11 +154,36 @@ impl ArchetypeDataPage {
         self.entities_ids.len() - 1
     }
 
+    /// Returns index of the first added entity
+    pub(crate) fn add_entity_ids(&mut self, ids: &[u32]) -> usize {
+        debug_assert!(self.entity_count() + ids.len() <= self.entity_capacity());
+        let first_index = self.entities_ids.len();
+        self.entities_ids.extend_from_slice(ids);
+        first_index
+    }
+
+    /// Removes entity from the page and runs drop glue for all of its components.
+    /// The last entity of the page takes place of the removed one
     pub(crate) fn swap_remove_entity_at_index(
         &mut self,
         index: usize,
         archetype: &Archetype,
         layout: &ArchetypeLayout
+    ) -> Option<SwapRemoveInfo> {
+        unsafe {
+            self.drop_component_data(index, archetype, layout);
+        }
+
+        self.swap_remove_moved_entity_at_index(index, archetype, layout)
+    }
+
+    /// Same as [`Self::swap_remove_entity_at_index`], but the entity components are treated
+    /// as already moved out of the page, so no drop glue runs for them
+    pub(crate) fn swap_remove_moved_entity_at_index(
+        &mut self,
+        index: usize,
+        archetype: &Archetype,
+        layout: &ArchetypeLayout
     ) -> Option<SwapRemoveInfo> {
         self.entities_ids.swap_remove(index);
         let last_swapped_index = self.entities_ids.len();
@@ -76,6 +200,41 @@ impl ArchetypeDataPage {
         }
     }
 
+    /// Runs drop glue for the components of all entities in the page
+    pub(crate) unsafe fn drop_all_component_data(
+        &mut self,
+        archetype: &Archetype,
+        layout: &ArchetypeLayout
+    ) {
+        if !archetype.needs_drop() {
+            return;
+        }
+
+        for index in 0..self.entity_count() {
+            self.drop_component_data(index, archetype, layout);
+        }
+    }
+
+    unsafe fn drop_component_data(
+        &self,
+        entity_index: usize,
+        archetype: &Archetype,
+        layout: &ArchetypeLayout
+    ) {
+        let sizes = archetype.component_sizes();
+        let offsets = layout.component_offsets();
+        let drop_fns = archetype.component_drop_fns();
+
+        for i in 0..archetype.component_count() {
+            if let Some(drop_fn) = *drop_fns.add(i) {
+                let size = *sizes.add(i);
+                let offset = *offsets.add(i);
+
+                drop_fn(self.get_component_data_ptr_mut(entity_index, offset, size));
+            }
+        }
+    }
+
     #[inline]
     unsafe fn move_component_data(
         &mut self,
@@ -94,20 +253,28 @@ impl ArchetypeDataPage {
             let src_comp = self.get_component_data_ptr(src_entity_index, offset, size);
             let dst_comp = self.get_component_data_ptr_mut(dst_entity_index, offset, size);
             src_comp.copy_to_nonoverlapping(dst_comp, size);
+
+            let added_tick = self.component_added_tick(i, src_entity_index);
+            self.set_component_added_tick(i, dst_entity_index, added_tick);
         }
     }
 
+    /// Moves components, that both archetypes contain, to the `dst` page and drops
+    /// the rest `src` components. After this the `src` entity must be removed without drop.
+    /// Components, which the `src` archetype doesn't contain, are marked as added with the tick
     #[inline]
-    pub unsafe fn copy_component_data_to_page_with_new_archetype(
-        src: &Self,
-        dst: &Self,
+    pub(crate) unsafe fn move_component_data_to_page_with_new_archetype(
+        src_view: &ArchetypeDataPageView,
         src_entity_index: usize,
+        dst_view: &ArchetypeDataPageView,
         dst_entity_index: usize,
-        src_archetype: &Archetype,
-        dst_archetype: &Archetype,
-        src_layout: &ArchetypeLayout,
-        dst_layout: &ArchetypeLayout
+        tick: u32
     ) {
+        let (src, src_archetype, src_layout) =
+            (src_view.page, src_view.archetype, src_view.layout);
+        let (dst, dst_archetype, dst_layout) =
+            (dst_view.page, dst_view.archetype, dst_view.layout);
+
         let arch_union = ArchetypesUnion::calculate(src_archetype, dst_archetype);
 
         for i in 0..arch_union.len() {
@@ -125,6 +292,94 @@ impl ArchetypeDataPage {
                 dst.get_component_data_ptr_mut(dst_entity_index, dst_offset, dst_size);
 
             src_ptr.copy_to_nonoverlapping(dst_ptr, dst_size);
+
+            let added_tick = src.component_added_tick(src_type_index, src_entity_index);
+            dst.set_component_added_tick(dst_type_index, dst_entity_index, added_tick);
+
+            let changed_tick = tick_utils::latest(
+                src.component_changed_tick(src_type_index),
+                dst.component_changed_tick(dst_type_index),
+                tick,
+            );
+            dst.mark_component_changed(dst_type_index, changed_tick);
+        }
+
+        for dst_type_index in 0..dst_archetype.component_count() {
+            if !arch_union.rhs_indices.contains(&dst_type_index) {
+                dst.set_component_added_tick(dst_type_index, dst_entity_index, tick);
+                dst.mark_component_changed(dst_type_index, tick);
+            }
+        }
+
+        let src_drop_fns = src_archetype.component_drop_fns();
+        for (i, &id) in src_archetype.included_ids().iter().enumerate() {
+            let drop_fn = match *src_drop_fns.add(i) {
+                Some(drop_fn) => drop_fn,
+                None => continue,
+            };
+
+            if dst_archetype.find_component_index(id).is_none() {
+                let offset = *src_layout.component_offsets().add(i);
+                let size = *src_archetype.component_sizes().add(i);
+                drop_fn(src.get_component_data_ptr_mut(src_entity_index, offset, size));
+            }
+        }
+    }
+
+    /// Clones the page with the components of all its entities. Columns without
+    /// clone glue in `clone_fns` are copied bitwise at once
+    pub(crate) unsafe fn clone_with_data(
+        &self,
+        archetype: &Archetype,
+        layout: &ArchetypeLayout,
+        clone_fns: &[Option<ComponentCloneFn>],
+    ) -> Self {
+        let page = self.clone_without_data();
+        let sizes = archetype.component_sizes();
+        let offsets = layout.component_offsets();
+        let entity_count = self.entity_count();
+
+        for (i, clone_fn) in clone_fns.iter().enumerate() {
+            let size = *sizes.add(i);
+            let offset = *offsets.add(i);
+
+            match clone_fn {
+                Some(clone_fn) => {
+                    for index in 0..entity_count {
+                        clone_fn(
+                            self.get_component_data_ptr(index, offset, size),
+                            page.get_component_data_ptr_mut(index, offset, size),
+                        );
+                    }
+                }
+                None => self
+                    .get_component_data_ptr(0, offset, size)
+                    .copy_to_nonoverlapping(
+                        page.get_component_data_ptr_mut(0, offset, size),
+                        size * entity_count,
+                    ),
+            }
+        }
+
+        page
+    }
+
+    fn clone_ticks(ticks: &[AtomicU32]) -> Vec<AtomicU32> {
+        ticks
+            .iter()
+            .map(|tick| AtomicU32::new(tick.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Clones the page state with the uninitialized components data
+    pub(crate) fn clone_without_data(&self) -> Self {
+        ArchetypeDataPage {
+            entities_ids: self.entities_ids.clone(),
+            entity_capacity: self.entity_capacity,
+            components_data_ptr: unsafe { mem_utils::alloc(self.size_bytes) },
+            size_bytes: self.size_bytes,
+            component_changed_ticks: Self::clone_ticks(&self.component_changed_ticks),
+            entity_added_ticks: Self::clone_ticks(&self.entity_added_ticks),
         }
     }
 
@@ -157,6 +412,6 @@ impl ArchetypeDataPage {
 
 impl Drop for ArchetypeDataPage {
     fn drop(&mut self) {
-        unsafe { mem_utils::dealloc(self.components_data_ptr, Self::PAGE_SIZE_BYTES) };
+        unsafe { mem_utils::dealloc(self.components_data_ptr, self.size_bytes) };
     }
 }
@@ -1,4 +1,7 @@
-use crate::{archetype_data_page::ArchetypeDataPage, component_tuple::ComponentTuple, Archetype, archetype_layout::ArchetypeLayout};
+use crate::{
+    archetype_data_page::ArchetypeDataPage, archetype_layout::ArchetypeLayout,
+    component_tuple::ComponentTuple, component_type::ComponentCloneFn, Archetype,
+};
 
 #[derive(Clone, Copy)]
 pub(crate) struct ArchetypeDataPageView<'a> {
@@ -7,7 +10,56 @@ pub(crate) struct ArchetypeDataPageView<'a> {
     pub page: &'a ArchetypeDataPage,
 }
 
+/// Drops the components, that are already cloned to the entity, if the next clone
+/// glue panics
+struct ClonedComponentsGuard<'a> {
+    view: &'a ArchetypeDataPageView<'a>,
+    entity_index: usize,
+    cloned_count: usize,
+}
+
 impl<'a> ArchetypeDataPageView<'a> {
+    /// Writes clones of the entity components to the reserved entity slot of the `dst`
+    /// page with the same archetype. Components without clone glue are copied bitwise.
+    /// If the clone glue panics, the cloned components are dropped and the slot
+    /// is left uninitialized
+    pub unsafe fn clone_components_to(
+        &self,
+        entity_index: usize,
+        dst: &ArchetypeDataPageView,
+        dst_entity_index: usize,
+        clone_fns: &[Option<ComponentCloneFn>],
+    ) {
+        let sizes = self.archetype.component_sizes();
+        let offsets = self.layout.component_offsets();
+        let dst_offsets = dst.layout.component_offsets();
+        let mut guard = ClonedComponentsGuard {
+            view: dst,
+            entity_index: dst_entity_index,
+            cloned_count: 0,
+        };
+
+        for (i, clone_fn) in clone_fns.iter().enumerate() {
+            let size = *sizes.add(i);
+            let src_ptr =
+                self.page
+                    .get_component_data_ptr(entity_index, *offsets.add(i), size);
+            let dst_ptr = dst.page.get_component_data_ptr_mut(
+                dst_entity_index,
+                *dst_offsets.add(i),
+                size,
+            );
+
+            match clone_fn {
+                Some(clone_fn) => clone_fn(src_ptr, dst_ptr),
+                None => src_ptr.copy_to_nonoverlapping(dst_ptr, size),
+            }
+            guard.cloned_count += 1;
+        }
+
+        std::mem::forget(guard);
+    }
+
     pub fn get_components_refs<T: ComponentTuple>(
         &self,
         page_entity_index: usize,
@@ -34,4 +86,71 @@ impl<'a> ArchetypeDataPageView<'a> {
             &T::get_offsets_unchecked(self.archetype, self.layout),
         )
     }
+
+    #[inline]
+    pub fn get_component_data_ptr(
+        &self,
+        component_index: usize,
+        page_entity_index: usize,
+    ) -> *const u8 {
+        unsafe {
+            self.page.get_component_data_ptr(
+                page_entity_index,
+                *self.layout.component_offsets().add(component_index),
+                *self.archetype.component_sizes().add(component_index),
+            )
+        }
+    }
+
+    /// Writes components to the uninitialized memory of the entity without dropping it
+    ///
+    /// # Safety
+    /// The page archetype must contain all components of `T`
+    pub unsafe fn write_components_unchecked<T: ComponentTuple>(
+        &self,
+        page_entity_index: usize,
+        components: T,
+    ) {
+        let offsets = T::get_offsets_unchecked(self.archetype, self.layout);
+        let ptrs =
+            T::add_to_ptrs_mut(&T::get_ptrs_mut(self.page, &offsets), page_entity_index);
+        T::write_to_ptrs(ptrs, components);
+    }
+
+    /// Drops components of `T`, that the page archetype contains
+    ///
+    /// # Safety
+    /// Dropped components must not be read or dropped again until they are rewritten
+    pub unsafe fn drop_components_in_place<T: ComponentTuple>(
+        &self,
+        page_entity_index: usize,
+    ) {
+        T::drop_included_in_place(
+            self.page,
+            page_entity_index,
+            self.archetype,
+            self.layout,
+        );
+    }
+}
+
+impl<'a> Drop for ClonedComponentsGuard<'a> {
+    fn drop(&mut self) {
+        let archetype = self.view.archetype;
+        unsafe {
+            let sizes = archetype.component_sizes();
+            let offsets = self.view.layout.component_offsets();
+            let drop_fns = archetype.component_drop_fns();
+
+            for i in 0..self.cloned_count {
+                if let Some(drop_fn) = *drop_fns.add(i) {
+                    drop_fn(self.view.page.get_component_data_ptr_mut(
+                        self.entity_index,
+                        *offsets.add(i),
+                        *sizes.add(i),
+                    ));
+                }
+            }
+        }
+    }
 }
@@ -1,36 +1,126 @@
-use crate::{Archetype, archetype_data_page::ArchetypeDataPage, mem_utils};
+use crate::{Archetype, mem_utils};
 
 #[derive(Debug)]
 pub struct ArchetypeLayout {
     offsets: *mut usize,
     component_count: usize,
     entities_capacity: usize,
+    page_size: usize,
 }
 
 impl ArchetypeLayout {
-    pub fn new(source_archetype: &Archetype) -> ArchetypeLayout {
+    /// Lays out the components in the page of `page_size` bytes. Archetypes, which
+    /// components don't fit into the page, get the dedicated page for a single entity
+    pub fn new(source_archetype: &Archetype, page_size: usize) -> ArchetypeLayout {
         let sizes = source_archetype.component_sizes();
         let aligns = source_archetype.component_aligns();
         let component_count = source_archetype.component_count();
 
-        let entities_capacity = unsafe {
-            Self::calculate_entities_capacity(sizes, aligns, component_count)
+        let (entities_capacity, page_size) = unsafe {
+            let mut entities_capacity = Self::calculate_entities_capacity(
+                sizes,
+                aligns,
+                component_count,
+                page_size,
+            );
+
+            // The capacity is approximate, alignment paddings may not fit the page
+            while entities_capacity > 0
+                && Self::calculate_required_page_size(
+                    sizes,
+                    aligns,
+                    component_count,
+                    entities_capacity,
+                )
+                .is_none_or(|required| required > page_size)
+            {
+                entities_capacity -= 1;
+            }
+
+            if entities_capacity > 0 {
+                (entities_capacity, page_size)
+            } else {
+                (1, Self::calculate_single_row_page_size(source_archetype))
+            }
         };
+
+        Self::with_capacity(source_archetype, entities_capacity, page_size)
+    }
+
+    /// Lays out the components of a single entity in the page of the exact size
+    pub fn single_row(source_archetype: &Archetype) -> ArchetypeLayout {
+        let page_size = unsafe { Self::calculate_single_row_page_size(source_archetype) };
+
+        // Page of the zero-sized components still gets the byte, so its allocation
+        // isn't zero-sized
+        Self::with_capacity(source_archetype, 1, page_size.max(1))
+    }
+
+    fn with_capacity(
+        source_archetype: &Archetype,
+        entities_capacity: usize,
+        page_size: usize,
+    ) -> ArchetypeLayout {
+        let component_count = source_archetype.component_count();
         let offsets = unsafe {
-            Self::calculate_offsets(sizes, aligns, component_count, entities_capacity)
+            Self::calculate_offsets(
+                source_archetype.component_sizes(),
+                source_archetype.component_aligns(),
+                component_count,
+                entities_capacity,
+                page_size,
+            )
         };
 
         Self {
             offsets,
             component_count,
-            entities_capacity
+            entities_capacity,
+            page_size,
+        }
+    }
+
+    unsafe fn calculate_single_row_page_size(source_archetype: &Archetype) -> usize {
+        Self::calculate_required_page_size(
+            source_archetype.component_sizes(),
+            source_archetype.component_aligns(),
+            source_archetype.component_count(),
+            1,
+        )
+        .filter(|&size| size <= isize::MAX as usize)
+        .unwrap_or_else(|| {
+            panic!(
+                "Components of the archetype {source_archetype} are too large to be laid out"
+            )
+        })
+    }
+
+    /// Exact size of the page with the components of `entities_capacity` entities.
+    /// `None` on the arithmetic overflow
+    unsafe fn calculate_required_page_size(
+        sizes: *const usize,
+        aligns: *const usize,
+        component_count: usize,
+        entities_capacity: usize,
+    ) -> Option<usize> {
+        let mut offset: usize = 0;
+
+        for i in 0..component_count {
+            let align = *aligns.add(i);
+            let column_size = (*sizes.add(i)).checked_mul(entities_capacity)?;
+
+            offset = offset.checked_next_multiple_of(align)?;
+            offset = offset.checked_add(column_size)?;
         }
+
+        Some(offset)
     }
 
     unsafe fn calculate_entities_capacity(
         sizes: *const usize,
         aligns: *const usize,
         component_count: usize,
+        page_size: usize,
     ) -> usize {
         let ptr_size = std::mem::size_of::<usize>();
 
@@ -38,12 +128,19 @@ impl ArchetypeLayout {
         let max_align = aligns.into_iter().max().unwrap();
         let max_align = max_align % ptr_size;
 
+        // Zero-sized components don't take the page space
+        let sizes = std::slice::from_raw_parts(sizes, component_count);
+        let sized_component_count = sizes.iter().filter(|&&s| s > 0).count();
+        if sized_component_count == 0 {
+            return std::cmp::max(page_size / std::mem::size_of::<u32>(), 1);
+        }
+
         let bytes_per_components_row_approx =
-            ArchetypeDataPage::PAGE_SIZE_BYTES / component_count - max_align;
+            (page_size / sized_component_count).saturating_sub(max_align);
 
-        let sizes = std::slice::from_raw_parts(sizes, component_count);
         let entities_capacity = sizes
             .iter()
+            .filter(|&&s| s > 0)
             .map(|s| bytes_per_components_row_approx / *s)
             .min()
             .unwrap();
@@ -56,6 +153,7 @@ impl ArchetypeLayout {
         aligns: *const usize,
         component_count: usize,
         entities_capacity: usize,
+        page_size: usize,
     ) -> *mut usize {
         let component_offsets: *mut usize = mem_utils::alloc(component_count);
         let mut offset = 0;
@@ -74,21 +172,50 @@ impl ArchetypeLayout {
             offset += size * entities_capacity;
         }
 
-        assert!(offset <= ArchetypeDataPage::PAGE_SIZE_BYTES);
+        assert!(offset <= page_size);
         component_offsets
     }
 
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
     #[inline]
     pub fn entities_capacity(&self) -> usize {
         self.entities_capacity
     }
 
+    /// Size of the data page in bytes, which fits the components of the layout
+    #[inline]
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
     #[inline]
     pub fn component_offsets(&self) -> *const usize {
         self.offsets
     }
 }
 
+impl Clone for ArchetypeLayout {
+    fn clone(&self) -> Self {
+        let offsets = unsafe {
+            let offsets = mem_utils::alloc(self.component_count);
+            self.offsets
+                .copy_to_nonoverlapping(offsets, self.component_count);
+            offsets
+        };
+
+        Self {
+            offsets,
+            component_count: self.component_count,
+            entities_capacity: self.entities_capacity,
+            page_size: self.page_size,
+        }
+    }
+}
+
 impl Drop for ArchetypeLayout {
     fn drop(&mut self) {
         unsafe {
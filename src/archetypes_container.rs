@@ -1,13 +1,27 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
 use crate::{
     archetype::Archetype,
+    archetype_builder::ArchetypeBuilder,
     archetype_data_page::{ArchetypeDataPage, SwapRemoveInfo},
     archetype_data_page_view::ArchetypeDataPageView,
     archetype_layout::ArchetypeLayout,
+    component_clone_fns::ComponentCloneFns,
+    component_tuple::ComponentTuple,
+    component_type::{ComponentCloneFn, ComponentId, ComponentType},
     entity_in_archetype::EntityInArchetype,
+    store_config::StoreConfig,
 };
 
+static NEXT_CONTAINER_ID: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug)]
 pub(crate) struct ArchetypesContainer {
+    id: u64,
+
     archetypes: Vec<Archetype>,
     layouts: Vec<ArchetypeLayout>,
     archetype_to_pages: Vec<ArchetypePages>,
@@ -17,9 +31,21 @@ pub(crate) struct ArchetypesContainer {
 
     free_archetypes: Vec<usize>,
     free_pages: Vec<usize>,
+
+    page_size: usize,
+    archetype_page_sizes: Vec<(Archetype, usize)>,
+
+    /// Increments every time an archetype slot gets a new archetype
+    generation: u64,
+    archetype_generations: Vec<u64>,
+
+    /// Cached transitions: archetype index and added (removed) components tuple type
+    /// to the index of the resulting archetype
+    add_edges: HashMap<(usize, ComponentId), usize>,
+    remove_edges: HashMap<(usize, ComponentId), usize>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct ArchetypePages {
     pages: Vec<usize>,
 }
@@ -28,14 +54,14 @@ impl ArchetypesContainer {
     const ARCHETYPE_DEFAULT_CAPACITY: usize = 5;
     const ARCHETYPE_PAGE_DEFAULT_CAPACITY: usize = 5;
 
-    pub fn new() -> ArchetypesContainer {
+    pub fn new(config: &StoreConfig) -> ArchetypesContainer {
         let archetypes = Vec::with_capacity(Self::ARCHETYPE_DEFAULT_CAPACITY);
         let layouts = Vec::with_capacity(Self::ARCHETYPE_DEFAULT_CAPACITY);
 
         let archetype_to_pages = Vec::with_capacity(Self::ARCHETYPE_DEFAULT_CAPACITY);
 
         let pages = (0..Self::ARCHETYPE_PAGE_DEFAULT_CAPACITY)
-            .map(|_| ArchetypeDataPage::new())
+            .map(|_| ArchetypeDataPage::new(config.page_size))
             .collect();
 
         let page_to_archetype = (0..Self::ARCHETYPE_PAGE_DEFAULT_CAPACITY)
@@ -46,6 +72,8 @@ impl ArchetypesContainer {
         let free_pages = (0..Self::ARCHETYPE_PAGE_DEFAULT_CAPACITY).collect();
 
         ArchetypesContainer {
+            id: NEXT_CONTAINER_ID.fetch_add(1, Ordering::Relaxed),
+
             archetypes,
             layouts,
             archetype_to_pages,
@@ -55,6 +83,15 @@ impl ArchetypesContainer {
 
             free_archetypes,
             free_pages,
+
+            page_size: config.page_size,
+            archetype_page_sizes: config.archetype_page_sizes.clone(),
+
+            generation: 0,
+            archetype_generations: Vec::with_capacity(Self::ARCHETYPE_DEFAULT_CAPACITY),
+
+            add_edges: HashMap::new(),
+            remove_edges: HashMap::new(),
         }
     }
 
@@ -62,14 +99,46 @@ impl ArchetypesContainer {
         &mut self,
         entity_id: u32,
         archetype: &Archetype,
+        tick: u32,
     ) -> EntityInArchetype {
         let archetype_index = self.reserve_archetype(archetype);
-        self.reserve_page(entity_id, archetype_index)
+        self.add_entity_to_archetype_index(entity_id, archetype_index, tick)
+    }
+
+    /// Adds entity to the archetype, that is already reserved with [`Self::reserve_archetype`]
+    pub fn add_entity_to_archetype_index(
+        &mut self,
+        entity_id: u32,
+        archetype_index: usize,
+        tick: u32,
+    ) -> EntityInArchetype {
+        let entity_in_arch = self.reserve_page(entity_id, archetype_index);
+
+        self.pages[entity_in_arch.page_index as usize]
+            .mark_entity_added(entity_in_arch.index_in_page as usize, tick);
+
+        entity_in_arch
     }
 
     pub fn swap_remove_entity(
         &mut self,
         entity_in_archetype: EntityInArchetype,
+    ) -> Option<SwapRemoveInfo> {
+        self.swap_remove_entity_internal(entity_in_archetype, true)
+    }
+
+    /// Removes entity, which components were moved to the other archetype, without drop
+    pub fn swap_remove_moved_entity(
+        &mut self,
+        entity_in_archetype: EntityInArchetype,
+    ) -> Option<SwapRemoveInfo> {
+        self.swap_remove_entity_internal(entity_in_archetype, false)
+    }
+
+    fn swap_remove_entity_internal(
+        &mut self,
+        entity_in_archetype: EntityInArchetype,
+        drop_components: bool,
     ) -> Option<SwapRemoveInfo> {
         let page_index = entity_in_archetype.page_index as usize;
         let arch_index = self.page_to_archetype[page_index];
@@ -79,11 +148,12 @@ impl ArchetypesContainer {
 
         let arch = &self.archetypes[arch_index];
         let layout = &self.layouts[arch_index];
-        let swap_remove = page.swap_remove_entity_at_index(
-            entity_in_archetype.index_in_page as usize,
-            &arch,
-            &layout,
-        );
+        let index_in_page = entity_in_archetype.index_in_page as usize;
+        let swap_remove = if drop_components {
+            page.swap_remove_entity_at_index(index_in_page, arch, layout)
+        } else {
+            page.swap_remove_moved_entity_at_index(index_in_page, arch, layout)
+        };
 
         if page_will_empty {
             self.free_page(page_index);
@@ -97,34 +167,122 @@ impl ArchetypesContainer {
         entity_id: u32,
         previous_entity_in_archetype: EntityInArchetype,
         previous_archetype_index: usize,
-        new_archetype: &Archetype,
+        new_archetype_index: usize,
+        tick: u32,
     ) -> EntityInArchetype {
-        let new_archetype_index = self.reserve_archetype(new_archetype);
         let new_entity_in_arch = self.reserve_page(entity_id, new_archetype_index);
 
-        let prev_page = &self.pages[previous_entity_in_archetype.page_index as usize];
-        let new_page = &self.pages[new_entity_in_arch.page_index as usize];
-        let prev_archetype = &self.archetypes[previous_archetype_index];
-
-        let prev_layout = &self.layouts[previous_archetype_index];
-        let new_layout = &self.layouts[new_archetype_index];
+        let prev_view = ArchetypeDataPageView {
+            archetype: &self.archetypes[previous_archetype_index],
+            layout: &self.layouts[previous_archetype_index],
+            page: &self.pages[previous_entity_in_archetype.page_index as usize],
+        };
+        let new_view = ArchetypeDataPageView {
+            archetype: &self.archetypes[new_archetype_index],
+            layout: &self.layouts[new_archetype_index],
+            page: &self.pages[new_entity_in_arch.page_index as usize],
+        };
 
         unsafe {
-            ArchetypeDataPage::copy_component_data_to_page_with_new_archetype(
-                prev_page,
-                new_page,
+            ArchetypeDataPage::move_component_data_to_page_with_new_archetype(
+                &prev_view,
                 previous_entity_in_archetype.index_in_page as usize,
+                &new_view,
                 new_entity_in_arch.index_in_page as usize,
-                prev_archetype,
-                new_archetype,
-                prev_layout,
-                new_layout,
+                tick,
             );
         }
 
         new_entity_in_arch
     }
 
+    /// Writes clones of the `src` entity components to the `dst` entity, which is
+    /// added to the same archetype. Clone glue is resolved by [`ComponentCloneFns::resolve`]
+    pub fn clone_entity_components(
+        &self,
+        src: EntityInArchetype,
+        dst: EntityInArchetype,
+        archetype_clone_fns: &[Option<ComponentCloneFn>],
+    ) {
+        unsafe {
+            let src_view = self.get_page_view_unchecked(src.page_index as usize);
+            let dst_view = self.get_page_view_unchecked(dst.page_index as usize);
+            src_view.clone_components_to(
+                src.index_in_page as usize,
+                &dst_view,
+                dst.index_in_page as usize,
+                archetype_clone_fns,
+            );
+        }
+    }
+
+    /// Clones the archetypes with the components of all entities. The clone gets
+    /// the new id, so the data cached for this container isn't valid for it
+    pub fn clone_with(&self, clone_fns: &ComponentCloneFns) -> Self {
+        // Clone glue is resolved before any component is cloned, so the missing
+        // one doesn't leave the half-cloned pages
+        let archetype_clone_fns: Vec<_> = self
+            .archetypes
+            .iter()
+            .zip(&self.archetype_to_pages)
+            .map(|(archetype, arch_pages)| {
+                let has_entities = arch_pages
+                    .pages
+                    .iter()
+                    .any(|&page_index| self.pages[page_index].entity_count() > 0);
+
+                if has_entities {
+                    clone_fns.resolve(archetype)
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        let pages = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(page_index, page)| {
+                if page.entity_count() == 0 {
+                    return page.clone_without_data();
+                }
+
+                let arch_index = self.page_to_archetype[page_index];
+                unsafe {
+                    page.clone_with_data(
+                        &self.archetypes[arch_index],
+                        &self.layouts[arch_index],
+                        &archetype_clone_fns[arch_index],
+                    )
+                }
+            })
+            .collect();
+
+        ArchetypesContainer {
+            id: NEXT_CONTAINER_ID.fetch_add(1, Ordering::Relaxed),
+
+            archetypes: self.archetypes.clone(),
+            layouts: self.layouts.clone(),
+            archetype_to_pages: self.archetype_to_pages.clone(),
+
+            pages,
+            page_to_archetype: self.page_to_archetype.clone(),
+
+            free_archetypes: self.free_archetypes.clone(),
+            free_pages: self.free_pages.clone(),
+
+            page_size: self.page_size,
+            archetype_page_sizes: self.archetype_page_sizes.clone(),
+
+            generation: self.generation,
+            archetype_generations: self.archetype_generations.clone(),
+
+            add_edges: self.add_edges.clone(),
+            remove_edges: self.remove_edges.clone(),
+        }
+    }
+
     #[inline(always)]
     pub unsafe fn get_page_view_unchecked(
         &self,
@@ -138,6 +296,23 @@ impl ArchetypesContainer {
         }
     }
 
+    /// Unique id of the container, used to validate data cached outside
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Generation, when the archetype was placed to the slot with the index
+    #[inline]
+    pub fn get_archetype_generation(&self, archetype_index: usize) -> u64 {
+        self.archetype_generations[archetype_index]
+    }
+
     #[inline]
     pub fn get_archetypes(&self) -> &[Archetype] {
         &self.archetypes
@@ -204,23 +379,118 @@ impl ArchetypesContainer {
         self.free_pages.push(page_index);
     }
 
-    fn reserve_archetype(&mut self, archetype: &Archetype) -> usize {
+    /// Reserves the archetype with the components of the archetype at the index and `T`.
+    /// Repeated transitions are resolved by the cached edge without allocations
+    pub fn reserve_archetype_with_added<T: ComponentTuple + 'static>(
+        &mut self,
+        archetype_index: usize,
+    ) -> usize {
+        let edge = (archetype_index, ComponentId::of::<T>());
+        if let Some(&new_archetype_index) = self.add_edges.get(&edge) {
+            self.unfree_archetype(new_archetype_index);
+            return new_archetype_index;
+        }
+
+        let new_archetype = ArchetypeBuilder::new()
+            .include_archetype(&self.archetypes[archetype_index])
+            .include_components::<T>()
+            .build();
+        let new_archetype_index = self.reserve_archetype(&new_archetype);
+        self.add_edges.insert(edge, new_archetype_index);
+
+        new_archetype_index
+    }
+
+    /// Reserves the archetype with the components of the archetype at the index
+    /// without `T`. Repeated transitions are resolved by the cached edge
+    pub fn reserve_archetype_with_removed<T: ComponentTuple + 'static>(
+        &mut self,
+        archetype_index: usize,
+    ) -> usize {
+        let edge = (archetype_index, ComponentId::of::<T>());
+        if let Some(&new_archetype_index) = self.remove_edges.get(&edge) {
+            self.unfree_archetype(new_archetype_index);
+            return new_archetype_index;
+        }
+
+        let new_archetype = ArchetypeBuilder::new()
+            .include_archetype(&self.archetypes[archetype_index])
+            .exclude_components::<T>()
+            .build();
+        let new_archetype_index = self.reserve_archetype(&new_archetype);
+        self.remove_edges.insert(edge, new_archetype_index);
+
+        new_archetype_index
+    }
+
+    /// Same as [`Self::reserve_archetype_with_added`] for the single component type.
+    /// The edge of the single component type is shared with the generic one
+    pub fn reserve_archetype_with_added_type(
+        &mut self,
+        archetype_index: usize,
+        component_type: &ComponentType,
+    ) -> usize {
+        let edge = (archetype_index, component_type.id());
+        if let Some(&new_archetype_index) = self.add_edges.get(&edge) {
+            self.unfree_archetype(new_archetype_index);
+            return new_archetype_index;
+        }
+
+        let new_archetype = ArchetypeBuilder::new()
+            .include_archetype(&self.archetypes[archetype_index])
+            .include_component_types(&[*component_type])
+            .build();
+        let new_archetype_index = self.reserve_archetype(&new_archetype);
+        self.add_edges.insert(edge, new_archetype_index);
+
+        new_archetype_index
+    }
+
+    /// Same as [`Self::reserve_archetype_with_removed`] for the single component type
+    pub fn reserve_archetype_with_removed_type(
+        &mut self,
+        archetype_index: usize,
+        component_type: &ComponentType,
+    ) -> usize {
+        let edge = (archetype_index, component_type.id());
+        if let Some(&new_archetype_index) = self.remove_edges.get(&edge) {
+            self.unfree_archetype(new_archetype_index);
+            return new_archetype_index;
+        }
+
+        let new_archetype = ArchetypeBuilder::new()
+            .include_archetype(&self.archetypes[archetype_index])
+            .exclude_component_types(&[*component_type])
+            .build();
+        let new_archetype_index = self.reserve_archetype(&new_archetype);
+        self.remove_edges.insert(edge, new_archetype_index);
+
+        new_archetype_index
+    }
+
+    pub fn reserve_archetype(&mut self, archetype: &Archetype) -> usize {
         for (i, arch) in self.archetypes.iter().enumerate() {
             if arch.is_same_as(&archetype) {
-                match self.free_archetypes.binary_search(&i) {
-                    Ok(i) => { self.free_archetypes.remove(i); },
-                    Err(_) => {}
-                };
-
+                self.unfree_archetype(i);
                 return i;
             }
         }
 
-        let layout = ArchetypeLayout::new(archetype);
+        let page_size = self.get_archetype_page_size(archetype);
+        let layout = ArchetypeLayout::new(archetype, page_size);
+        self.generation += 1;
+
         match self.free_archetypes.pop() {
             Some(free_idx) => {
+                // Edges of the previous archetype in the slot are not valid anymore
+                self.add_edges
+                    .retain(|&(from, _), &mut to| from != free_idx && to != free_idx);
+                self.remove_edges
+                    .retain(|&(from, _), &mut to| from != free_idx && to != free_idx);
+
                 self.archetypes[free_idx] = archetype.clone();
                 self.layouts[free_idx] = layout;
+                self.archetype_generations[free_idx] = self.generation;
 
                 free_idx
             }
@@ -229,32 +499,71 @@ impl ArchetypesContainer {
                 self.layouts.push(layout);
                 self.archetype_to_pages
                     .push(ArchetypePages { pages: Vec::new() });
+                self.archetype_generations.push(self.generation);
 
                 self.archetypes.len() - 1
             }
         }
     }
 
+    pub fn get_archetype_page_size(&self, archetype: &Archetype) -> usize {
+        self.archetype_page_sizes
+            .iter()
+            .find(|(arch, _)| arch.is_same_as(archetype))
+            .map_or(self.page_size, |(_, size)| *size)
+    }
+
+    fn unfree_archetype(&mut self, archetype_index: usize) {
+        if let Some(i) = self
+            .free_archetypes
+            .iter()
+            .position(|&free_idx| free_idx == archetype_index)
+        {
+            self.free_archetypes.swap_remove(i);
+        }
+    }
+
+    /// Adds entities to the free slots of the page, that is reserved with
+    /// [`Self::reserve_page_with_free_space`]. Returns index of the first entity
+    pub fn add_entities_to_page(
+        &mut self,
+        page_index: usize,
+        entity_ids: &[u32],
+        tick: u32,
+    ) -> usize {
+        let page = &mut self.pages[page_index];
+        let first_index = page.add_entity_ids(entity_ids);
+        for index_in_page in first_index..first_index + entity_ids.len() {
+            page.mark_entity_added(index_in_page, tick);
+        }
+
+        first_index
+    }
+
     fn reserve_page(
         &mut self,
         entity_id: u32,
         archetype_index: usize,
     ) -> EntityInArchetype {
-        let pages = &mut self.archetype_to_pages[archetype_index].pages;
+        let page_index = self.reserve_page_with_free_space(archetype_index);
+        let index_in_page = self.pages[page_index].add_entity_id(entity_id) as u32;
+
+        EntityInArchetype {
+            page_index: page_index as u32,
+            index_in_page,
+        }
+    }
+
+    /// Index of the archetype page with free slots. The new page is taken, when all
+    /// pages of the archetype are full
+    pub fn reserve_page_with_free_space(&mut self, archetype_index: usize) -> usize {
+        let pages = &self.archetype_to_pages[archetype_index].pages;
 
         // Looking in reverse direction, because there is more probability, that
         // page with free slots will be located in the end
-        for page_index in pages.into_iter().rev() {
-            let page_index = *page_index;
-            let page = &mut self.pages[page_index];
-            let page_index = page_index as u32;
-            if page.has_free_space() {
-                let index_in_page = page.add_entity_id(entity_id) as u32;
-
-                return EntityInArchetype {
-                    page_index,
-                    index_in_page,
-                };
+        for &page_index in pages.iter().rev() {
+            if self.pages[page_index].has_free_space() {
+                return page_index;
             }
         }
 
@@ -262,7 +571,10 @@ impl ArchetypesContainer {
             Some(page_index) => page_index,
             None => {
                 let page_index = self.pages.len();
-                self.pages.insert(page_index, ArchetypeDataPage::new());
+                self.pages.insert(
+                    page_index,
+                    ArchetypeDataPage::new(self.layouts[archetype_index].page_size()),
+                );
                 self.page_to_archetype.insert(page_index, archetype_index);
                 page_index
             }
@@ -276,12 +588,24 @@ impl ArchetypesContainer {
             .push(page_index);
         self.page_to_archetype[page_index] = archetype_index;
 
-        let index_in_page = self.pages[page_index].add_entity_id(entity_id) as u32;
-        let page_index = page_index as u32;
+        page_index
+    }
+}
 
-        EntityInArchetype {
-            page_index,
-            index_in_page,
+impl Drop for ArchetypesContainer {
+    fn drop(&mut self) {
+        for (page_index, page) in self.pages.iter_mut().enumerate() {
+            if page.entity_count() == 0 {
+                continue;
+            }
+
+            let arch_index = self.page_to_archetype[page_index];
+            unsafe {
+                page.drop_all_component_data(
+                    &self.archetypes[arch_index],
+                    &self.layouts[arch_index],
+                );
+            }
         }
     }
 }
@@ -70,6 +70,15 @@ pub unsafe fn is_bit_on(ptr: *const u32, bit_index: usize) -> bool {
     (val & on_mask) == on_mask
 }
 
+/// Bits out of the words slice are off
+#[inline]
+pub fn is_bit_on_in_words(words: &[u32], bit_index: usize) -> bool {
+    let base_bits = get_bitvec_base_bits();
+    words
+        .get(bit_index / base_bits)
+        .is_some_and(|word| word & (1 << (bit_index % base_bits)) != 0)
+}
+
 #[inline]
 pub fn get_len(elements: usize) -> usize {
     elements / get_bitvec_base_bits() + 1
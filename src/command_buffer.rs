@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{component_tuple::ComponentTuple, Entity, Store, Tag};
+
+type EntityCommandFn = Box<dyn FnOnce(&mut Store, Entity) + Send>;
+type StoreCommandFn = Box<dyn FnOnce(&mut Store) + Send>;
+
+/// Ids of the reserved entities are unique between all command buffers,
+/// so the buffer can check, that the reserved entity is its own
+static NEXT_RESERVED_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Records structural changes of the store (entities creation/destroying,
+/// components and tags adding/removing) and sent events to apply them later with
+/// [`Store::apply_commands`]. Useful, when the store is borrowed by a query iteration
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+    reserved_id_to_index: HashMap<u32, usize>,
+    reserved_to_created: Vec<Entity>,
+}
+
+enum Command {
+    Create,
+    Destroy(Entity),
+    Modify(Entity, EntityCommandFn),
+    Store(StoreCommandFn),
+}
+
+impl CommandBuffer {
+    /// Version of the reserved entities. Alive entities of the store never have it
+    const RESERVED_ENTITY_VERSION: u32 = 0;
+
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            reserved_id_to_index: HashMap::new(),
+            reserved_to_created: Vec::new(),
+        }
+    }
+
+    /// Reserves an entity, that will be created by the store, when commands are applied.
+    /// Returned entity can be used only with commands of this buffer until they are
+    /// applied, other buffers panic on it
+    pub fn create_entity(&mut self) -> Entity {
+        let id = loop {
+            let id = NEXT_RESERVED_ID.fetch_add(1, Ordering::Relaxed);
+            if id != Entity::NULL_ID {
+                break id;
+            }
+        };
+
+        let index = self.reserved_id_to_index.len();
+        self.reserved_id_to_index.insert(id, index);
+        self.commands.push(Command::Create);
+
+        Entity {
+            id,
+            version: Self::RESERVED_ENTITY_VERSION,
+        }
+    }
+
+    pub fn destroy_entity(&mut self, entity: Entity) {
+        self.assert_own_reserved(entity);
+        self.commands.push(Command::Destroy(entity));
+    }
+
+    pub fn add_components<T: ComponentTuple + Send + 'static>(
+        &mut self,
+        entity: Entity,
+        components: T,
+    ) {
+        self.push_entity_command(entity, move |store, e| {
+            store.add_components(e, components)
+        });
+    }
+
+    pub fn remove_components<T: ComponentTuple + 'static>(&mut self, entity: Entity) {
+        self.push_entity_command(entity, |store, e| store.remove_components::<T>(e));
+    }
+
+    pub fn add_tag<T: Tag>(&mut self, entity: Entity) {
+        self.push_entity_command(entity, |store, e| store.add_tag::<T>(e));
+    }
+
+    pub fn remove_tag<T: Tag>(&mut self, entity: Entity) {
+        self.push_entity_command(entity, |store, e| store.remove_tag::<T>(e));
+    }
+
+    /// Event is sent to the store queue, when commands are applied
+    pub fn send_event<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.commands
+            .push(Command::Store(Box::new(|store| store.send_event(event))));
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Discards all recorded commands and reserved entities
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.reserved_id_to_index.clear();
+    }
+
+    #[inline]
+    fn push_entity_command(
+        &mut self,
+        entity: Entity,
+        command: impl FnOnce(&mut Store, Entity) + Send + 'static,
+    ) {
+        self.assert_own_reserved(entity);
+        self.commands
+            .push(Command::Modify(entity, Box::new(command)));
+    }
+
+    #[inline]
+    fn is_reserved(entity: Entity) -> bool {
+        entity.version == Self::RESERVED_ENTITY_VERSION && entity.id != Entity::NULL_ID
+    }
+
+    #[inline]
+    fn assert_own_reserved(&self, entity: Entity) {
+        assert!(
+            !Self::is_reserved(entity)
+                || self.reserved_id_to_index.contains_key(&entity.id),
+            "Entity {} isn't reserved by this command buffer",
+            entity.id
+        );
+    }
+
+    #[inline]
+    fn resolve_entity(&self, entity: Entity) -> Entity {
+        if Self::is_reserved(entity) {
+            self.reserved_to_created[self.reserved_id_to_index[&entity.id]]
+        } else {
+            entity
+        }
+    }
+}
+
+impl Default for CommandBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Store {
+    /// Applies commands of the buffer in the recording order and clears it.
+    /// Commands for the entities, that are already dead, are skipped
+    pub fn apply_commands(&mut self, commands: &mut CommandBuffer) {
+        commands.reserved_to_created.clear();
+
+        let mut recorded = std::mem::take(&mut commands.commands);
+        for command in recorded.drain(..) {
+            match command {
+                Command::Create => {
+                    let entity = self.create_entity();
+                    commands.reserved_to_created.push(entity);
+                }
+                Command::Destroy(entity) => {
+                    let entity = commands.resolve_entity(entity);
+                    if self.is_alive(entity) {
+                        self.destroy_entity(entity);
+                    }
+                }
+                Command::Modify(entity, command) => {
+                    let entity = commands.resolve_entity(entity);
+                    if self.is_alive(entity) {
+                        command(self, entity);
+                    }
+                }
+                Command::Store(command) => command(self),
+            }
+        }
+
+        commands.commands = recorded;
+        commands.clear();
+    }
+}
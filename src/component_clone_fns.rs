@@ -0,0 +1,65 @@
+use crate::{component_type::ComponentCloneFn, Archetype, Component, ComponentId, Store};
+
+/// Clone glue of the component types, registered with [`Store::register_clone`].
+/// Runtime types carry it in their descriptors. Components without drop glue
+/// don't need it, they are copied bitwise
+#[derive(Clone, Default)]
+pub(crate) struct ComponentCloneFns {
+    type_ids: Vec<ComponentId>,
+    clone_fns: Vec<ComponentCloneFn>,
+}
+
+impl ComponentCloneFns {
+    /// Sets the clone glue of the component type, replacing the previous one
+    pub fn register<T: Clone + 'static>(&mut self) {
+        let type_id = ComponentId::of::<T>();
+        let clone_fn: ComponentCloneFn = Self::clone_to::<T>;
+        match self.type_ids.binary_search(&type_id) {
+            Ok(i) => self.clone_fns[i] = clone_fn,
+            Err(i) => {
+                self.type_ids.insert(i, type_id);
+                self.clone_fns.insert(i, clone_fn);
+            }
+        }
+    }
+
+    pub fn get(&self, type_id: ComponentId) -> Option<ComponentCloneFn> {
+        self.type_ids
+            .binary_search(&type_id)
+            .ok()
+            .map(|i| self.clone_fns[i])
+    }
+
+    /// Clone glue of every archetype component, `None` for the bitwise copy.
+    /// Panics, if the component with drop glue isn't registered
+    pub fn resolve(&self, archetype: &Archetype) -> Vec<Option<ComponentCloneFn>> {
+        archetype
+            .components_iter()
+            .map(|component| {
+                let clone_fn = self.get(component.id()).or(component.clone_fn());
+                assert!(
+                    clone_fn.is_some() || component.drop_fn().is_none(),
+                    "Component {} needs drop and can't be cloned without Store::register_clone",
+                    component.name()
+                );
+
+                clone_fn
+            })
+            .collect()
+    }
+
+    unsafe fn clone_to<T: Clone>(src: *const u8, dst: *mut u8) {
+        (dst as *mut T).write((*(src as *const T)).clone());
+    }
+}
+
+impl Store {
+    /// Registers the clone glue of the component for [`Store::clone_entity`] and
+    /// [`Clone`] of the store. It's required for the components with drop glue,
+    /// the rest are copied bitwise without it. So the component without drop glue,
+    /// which `Clone` isn't the bitwise copy (e.g. it counts the handles), must be
+    /// registered too, otherwise its `Clone` is silently skipped
+    pub fn register_clone<T: Component + Clone + 'static>(&mut self) {
+        self.component_clone_fns.register::<T>();
+    }
+}
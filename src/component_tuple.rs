@@ -1,8 +1,8 @@
 use paste::paste;
 
 use crate::{
-    archetype_data_page::ArchetypeDataPage, archetype_layout::ArchetypeLayout, type_ids,
-    Archetype, ArchetypeBuilder, Component,
+    archetype_data_page::ArchetypeDataPage, archetype_layout::ArchetypeLayout,
+    component_ids, Archetype, ArchetypeBuilder, Component, ComponentId,
 };
 
 pub trait ComponentTuple {
@@ -55,8 +55,32 @@ pub trait ComponentTuple {
 
     fn assign_to_refs<'a>(refs: Self::MutRefTuple<'a>, values: Self);
 
+    /// Writes values to the uninitialized components memory without dropping it
+    ///
+    /// # Safety
+    /// Pointers must be valid for writes
+    unsafe fn write_to_ptrs(ptrs: Self::MutPtrTuple, values: Self);
+
+    /// Runs drop glue for the tuple components, which are included in the archetype
+    ///
+    /// # Safety
+    /// The page and layout must belong to the archetype and the components
+    /// must be initialized
+    unsafe fn drop_included_in_place(
+        page: &ArchetypeDataPage,
+        entity_index: usize,
+        archetype: &Archetype,
+        layout: &ArchetypeLayout,
+    );
+
+    /// Marks the tuple components in the page as changed with the tick
+    fn mark_changed_in_page(page: &ArchetypeDataPage, archetype: &Archetype, tick: u32);
+
     fn into_archetype() -> Archetype;
+    fn type_ids() -> Vec<ComponentId>;
     fn is_archetype_include_types(archetype: &Archetype) -> bool;
+    /// Whether the archetype contains none of the tuple components
+    fn is_archetype_exclude_types(archetype: &Archetype) -> bool;
 
     fn add_components_to_archetype_builder(
         builder: &mut ArchetypeBuilder,
@@ -248,6 +272,47 @@ macro_rules! components_tuple_impl {
                 );
             }
 
+            #[inline(always)]
+            unsafe fn write_to_ptrs(
+                ($( paste!([<$T:lower>]) ),*): <($($T),*) as ComponentTuple>::MutPtrTuple,
+                ($( paste!([<v_$T:lower>]) ),*): Self
+            ) {
+                $(
+                    paste!{ [<$T:lower>] }.write(paste!{ [<v_$T:lower>] });
+                )*
+            }
+
+            unsafe fn drop_included_in_place(
+                page: &ArchetypeDataPage,
+                entity_index: usize,
+                archetype: &Archetype,
+                layout: &ArchetypeLayout,
+            ) {
+                $(
+                    if let Some(idx) = archetype.find_component_index_of::<$T>() {
+                        let ptr = page.get_component_data_ptr_mut(
+                            entity_index,
+                            *layout.component_offsets().add(idx),
+                            std::mem::size_of::<$T>()
+                        ) as *mut $T;
+                        std::ptr::drop_in_place(ptr);
+                    }
+                )*
+            }
+
+            #[inline(always)]
+            fn mark_changed_in_page(
+                page: &ArchetypeDataPage,
+                archetype: &Archetype,
+                tick: u32
+            ) {
+                $(
+                    if let Some(idx) = archetype.find_component_index_of::<$T>() {
+                        page.mark_component_changed(idx, tick);
+                    }
+                )*
+            }
+
             fn into_archetype() -> $crate::Archetype
             {
                 let components = &mut $crate::component_types!( $($T),* );
@@ -255,9 +320,18 @@ macro_rules! components_tuple_impl {
                 $crate::Archetype::new(components)
             }
 
+            fn type_ids() -> Vec<ComponentId> {
+                component_ids!($($T),*).to_vec()
+            }
+
             #[inline(always)]
             fn is_archetype_include_types(archetype: &Archetype) -> bool {
-                archetype.is_include_ids(&type_ids!($($T),*))
+                archetype.is_include_ids(&component_ids!($($T),*))
+            }
+
+            #[inline(always)]
+            fn is_archetype_exclude_types(archetype: &Archetype) -> bool {
+                $( !archetype.has_component::<$T>() )&&*
             }
 
             fn add_components_to_archetype_builder(
@@ -278,14 +352,14 @@ macro_rules! components_tuple_impl {
         }
 
         #[allow(unused_parens)]
-        impl<$($T: Copy),*> ClonedExtension for ($(& $T),*) {
+        impl<$($T: Clone),*> ClonedExtension for ($(& $T),*) {
             type Output = ($($T),*);
             fn cloned<'a>(self) -> Self::Output {
                 let ($( paste!([<$T:lower>]) ),*) = self;
 
                 (
                     $(
-                        *paste!{ [<$T:lower>] }
+                        paste!{ [<$T:lower>] }.clone()
                     ),*
                 )
             }
@@ -1,42 +1,89 @@
 use std::any::TypeId;
 
+/// Type-erased drop glue of the component, that receives pointer to the component data
+pub type ComponentDropFn = unsafe fn(*mut u8);
+
+/// Type-erased clone glue of the component, that writes the clone of the `src`
+/// component to the uninitialized `dst`
+pub type ComponentCloneFn = unsafe fn(src: *const u8, dst: *mut u8);
+
+/// Id of the component type. Rust types are identified by their `TypeId`,
+/// runtime types get the unique index by [`ComponentType::dynamic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComponentId {
+    Type(TypeId),
+    Dynamic(u32),
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ComponentType {
-    id: TypeId,
+    id: ComponentId,
     size: usize,
     align: usize,
     name: &'static str,
+    drop_fn: Option<ComponentDropFn>,
+    clone_fn: Option<ComponentCloneFn>,
+}
+
+impl ComponentId {
+    #[inline(always)]
+    pub fn of<T: 'static>() -> Self {
+        Self::Type(TypeId::of::<T>())
+    }
+}
+
+impl From<TypeId> for ComponentId {
+    #[inline(always)]
+    fn from(type_id: TypeId) -> Self {
+        Self::Type(type_id)
+    }
 }
 
 impl ComponentType {
     pub fn of<T: 'static>() -> Self {
         ComponentType {
-            id: TypeId::of::<T>(),
+            id: ComponentId::of::<T>(),
             size: std::mem::size_of::<T>(),
             align: std::mem::align_of::<T>(),
             name: std::any::type_name::<T>(),
+            drop_fn: if std::mem::needs_drop::<T>() {
+                Some(Self::drop_in_place::<T>)
+            } else {
+                None
+            },
+            clone_fn: None,
         }
     }
 
     pub(crate) fn new(
-        id: TypeId,
+        id: ComponentId,
         size: usize,
         align: usize,
         name: &'static str,
+        drop_fn: Option<ComponentDropFn>,
+        clone_fn: Option<ComponentCloneFn>,
     ) -> ComponentType {
         ComponentType {
             id,
             size,
             align,
             name,
+            drop_fn,
+            clone_fn,
         }
     }
 
     #[inline(always)]
-    pub fn id(&self) -> TypeId {
+    pub fn id(&self) -> ComponentId {
         self.id
     }
 
+    /// Whether the type is created at runtime by [`ComponentType::dynamic`]
+    #[inline(always)]
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self.id, ComponentId::Dynamic(_))
+    }
+
     #[inline(always)]
     pub fn size(&self) -> usize {
         self.size
@@ -51,6 +98,23 @@ impl ComponentType {
     pub fn name(&self) -> &'static str {
         self.name
     }
+
+    /// Drop glue of the component. It's `None` for the types, that don't need drop
+    #[inline(always)]
+    pub fn drop_fn(&self) -> Option<ComponentDropFn> {
+        self.drop_fn
+    }
+
+    /// Clone glue of the runtime type. Rust types are registered with
+    /// [`crate::Store::register_clone`] instead
+    #[inline(always)]
+    pub fn clone_fn(&self) -> Option<ComponentCloneFn> {
+        self.clone_fn
+    }
+
+    unsafe fn drop_in_place<T>(ptr: *mut u8) {
+        std::ptr::drop_in_place(ptr as *mut T)
+    }
 }
 
 impl PartialEq for ComponentType {
@@ -1,5 +1,25 @@
 pub use division_ecs_attributes::{Component, Tag};
 
-pub trait Component: Clone + Copy + Sized {}
+use crate::ComponentType;
 
-pub trait Tag: 'static {}
\ No newline at end of file
+pub trait Component: Sized {}
+
+/// Marker, which is stored outside of the archetypes by default, so adding and
+/// removing it doesn't move the entity components.
+///
+/// Tag derived with `#[tag(archetype)]` is also the zero-sized archetype member.
+/// [`crate::Store::add_tag`] and [`crate::Store::remove_tag`] move the entity to the
+/// archetype with or without it, so the queries can match it as any other component
+/// (e.g. with [`crate::With`]) without per-entity checks of the tag filters
+pub trait Tag: 'static {
+    /// Whether the tag is stored in the archetype, set by `#[tag(archetype)]`
+    const IS_ARCHETYPE_TAG: bool = false;
+
+    /// Component type of the tag, when it's stored in the archetype
+    fn archetype_component() -> Option<ComponentType>
+    where
+        Self: Sized,
+    {
+        Self::IS_ARCHETYPE_TAG.then(ComponentType::of::<Self>)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,502 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::{
+    archetype_builder::ArchetypeBuilder,
+    archetype_data_page_view::ArchetypeDataPageView,
+    archetypes_container::ArchetypesContainer,
+    component_type::{ComponentCloneFn, ComponentDropFn},
+    ComponentId, ComponentType, Entity, Store,
+};
+
+/// Descriptors of the runtime types in the process, interned by the name.
+/// The index of the descriptor is its [`ComponentId::Dynamic`] id
+static DYNAMIC_TYPES: Mutex<Vec<ComponentType>> = Mutex::new(Vec::new());
+
+impl ComponentType {
+    /// Creates the descriptor of the component type, which is known only at runtime
+    /// (e.g. defined by the script). The value of the type is plain bytes, which are
+    /// accessed with [`Store::get_component_bytes`] and [`DynamicQueryPage::column`].
+    ///
+    /// The same name returns the same descriptor. The name is leaked for the process
+    /// lifetime. Panics, if `align` isn't power of two or `size` isn't multiple
+    /// of `align`, or if the name is already registered with other layout or glue
+    pub fn dynamic(
+        name: &str,
+        size: usize,
+        align: usize,
+        drop_fn: Option<ComponentDropFn>,
+        clone_fn: Option<ComponentCloneFn>,
+    ) -> ComponentType {
+        assert!(
+            align.is_power_of_two(),
+            "Component align must be power of two"
+        );
+        assert!(
+            size.is_multiple_of(align),
+            "Component size must be multiple of align"
+        );
+
+        let mut types = DYNAMIC_TYPES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(existing) = types.iter().find(|t| t.name() == name) {
+            assert!(
+                existing.size() == size && existing.align() == align,
+                "Dynamic component {} is already registered with other layout",
+                name
+            );
+            assert!(
+                existing.drop_fn().map(|f| f as usize) == drop_fn.map(|f| f as usize)
+                    && existing.clone_fn().map(|f| f as usize)
+                        == clone_fn.map(|f| f as usize),
+                "Dynamic component {} is already registered with other glue",
+                name
+            );
+            return *existing;
+        }
+
+        let index =
+            u32::try_from(types.len()).expect("Dynamic component types are exhausted");
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        let component_type = ComponentType::new(
+            ComponentId::Dynamic(index),
+            size,
+            align,
+            name,
+            drop_fn,
+            clone_fn,
+        );
+        types.push(component_type);
+
+        component_type
+    }
+
+    fn assert_dynamic(&self) {
+        assert!(
+            self.is_dynamic(),
+            "Bytes of component {} can contain uninitialized padding, only dynamic types are accessed as bytes",
+            self.name()
+        );
+    }
+}
+
+/// Query over the runtime component types. Columns of the matched pages are
+/// accessed as bytes in the order of [`DynamicQuery::read`] and [`DynamicQuery::write`]
+#[derive(Default)]
+pub struct DynamicQuery {
+    read_ids: Vec<ComponentId>,
+    write_ids: Vec<ComponentId>,
+    with_ids: Vec<ComponentId>,
+    without_ids: Vec<ComponentId>,
+
+    matched_archetypes: Vec<usize>,
+    archetypes_container_id: Option<u64>,
+    archetypes_generation: u64,
+}
+
+/// Iterator over the non-empty pages of the archetypes, matched by [`DynamicQuery`]
+pub struct DynamicQueryIter<'a> {
+    store: &'a Store,
+    query: &'a DynamicQuery,
+    tick: u32,
+    matched_index: usize,
+    page_index: usize,
+}
+
+/// Entities of the single page with the queried columns
+pub struct DynamicQueryPage<'a> {
+    store: &'a Store,
+    page_view: ArchetypeDataPageView<'a>,
+    read_indices: Vec<usize>,
+    write_indices: Vec<usize>,
+}
+
+impl DynamicQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Columns of the components are accessed as bytes, so only the runtime types
+    /// of [`ComponentType::dynamic`] are accepted
+    pub fn read(mut self, components: &[ComponentType]) -> Self {
+        components.iter().for_each(ComponentType::assert_dynamic);
+        Self::extend_ids(&mut self.read_ids, components);
+        self
+    }
+
+    /// Columns of the components are marked as changed by every iteration.
+    /// Only the runtime types of [`ComponentType::dynamic`] are accepted
+    pub fn write(mut self, components: &[ComponentType]) -> Self {
+        components.iter().for_each(ComponentType::assert_dynamic);
+        Self::extend_ids(&mut self.write_ids, components);
+        self
+    }
+
+    /// Keeps only the entities, that have all of the components
+    pub fn with(mut self, components: &[ComponentType]) -> Self {
+        Self::extend_ids(&mut self.with_ids, components);
+        self
+    }
+
+    /// Keeps only the entities, that have none of the components
+    pub fn without(mut self, components: &[ComponentType]) -> Self {
+        Self::extend_ids(&mut self.without_ids, components);
+        self
+    }
+
+    fn extend_ids(ids: &mut Vec<ComponentId>, components: &[ComponentType]) {
+        for component in components {
+            assert!(
+                !ids.contains(&component.id()),
+                "Component {} is queried twice",
+                component.name()
+            );
+            ids.push(component.id());
+        }
+    }
+
+    /// Checks only archetypes, that were created since the previous call.
+    /// The whole cache is rebuilt, when the query is used with the other store
+    fn update_matched_archetypes(&mut self, arch_container: &ArchetypesContainer) {
+        let generation = arch_container.generation();
+        if self.archetypes_container_id != Some(arch_container.id()) {
+            self.archetypes_container_id = Some(arch_container.id());
+            self.archetypes_generation = 0;
+            self.matched_archetypes.clear();
+        } else if self.archetypes_generation == generation {
+            return;
+        }
+
+        for arch_idx in 0..arch_container.get_archetypes().len() {
+            if arch_container.get_archetype_generation(arch_idx)
+                <= self.archetypes_generation
+            {
+                continue;
+            }
+
+            let position = self.matched_archetypes.binary_search(&arch_idx);
+            let arch = &arch_container.get_archetypes()[arch_idx];
+            let is_matched = arch.is_include_ids(&self.read_ids)
+                && arch.is_include_ids(&self.write_ids)
+                && arch.is_include_ids(&self.with_ids)
+                && self
+                    .without_ids
+                    .iter()
+                    .all(|&id| arch.find_component_index(id).is_none());
+
+            match (position, is_matched) {
+                (Ok(i), false) => {
+                    self.matched_archetypes.remove(i);
+                }
+                (Err(i), true) => self.matched_archetypes.insert(i, arch_idx),
+                _ => {}
+            }
+        }
+
+        self.archetypes_generation = generation;
+    }
+}
+
+impl<'a> Iterator for DynamicQueryIter<'a> {
+    type Item = DynamicQueryPage<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arch_container = &self.store.archetypes_container;
+
+        loop {
+            let &arch_idx = self.query.matched_archetypes.get(self.matched_index)?;
+            let page_indices = arch_container.get_archetype_page_indices(arch_idx);
+
+            let Some(&page_index) = page_indices.get(self.page_index) else {
+                self.matched_index += 1;
+                self.page_index = 0;
+                continue;
+            };
+            self.page_index += 1;
+
+            let page_view = unsafe { arch_container.get_page_view_unchecked(page_index) };
+            if page_view.page.entity_count() == 0 {
+                continue;
+            }
+
+            let find_indices = |ids: &[ComponentId]| -> Vec<usize> {
+                ids.iter()
+                    .map(|&id| page_view.archetype.find_component_index(id).unwrap())
+                    .collect()
+            };
+            let read_indices = find_indices(&self.query.read_ids);
+            let write_indices = find_indices(&self.query.write_ids);
+
+            for &component_index in &write_indices {
+                page_view
+                    .page
+                    .mark_component_changed(component_index, self.tick);
+            }
+
+            return Some(DynamicQueryPage {
+                store: self.store,
+                page_view,
+                read_indices,
+                write_indices,
+            });
+        }
+    }
+}
+
+impl<'a> DynamicQueryPage<'a> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.page_view.page.entity_count()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn entity(&self, index: usize) -> Entity {
+        assert!(index < self.len(), "Entity index is out of the page");
+        unsafe {
+            let id = *self.page_view.page.entity_id_ptrs().add(index);
+            self.store.get_entity_by_id_unchecked(id)
+        }
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        (0..self.len()).map(|index| self.entity(index))
+    }
+
+    /// Bytes of the read component column with the index of [`DynamicQuery::read`]
+    pub fn column(&self, read_index: usize) -> &[u8] {
+        let (ptr, len) = self.column_raw(self.read_indices[read_index]);
+        if len == 0 {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+
+    /// Bytes of the write component column with the index of [`DynamicQuery::write`]
+    ///
+    /// # Safety
+    /// The bytes of every value in the column must stay the valid value of the
+    /// component type, e.g. the handle, which the drop glue releases, must not be
+    /// overwritten with the garbage. [`Store::dynamic_query_iter`] shares the store,
+    /// so while the column is borrowed, no other live iterator over the same
+    /// archetype may access the same column or the bytes of its components
+    pub unsafe fn column_mut(&mut self, write_index: usize) -> &mut [u8] {
+        let (ptr, len) = self.column_raw(self.write_indices[write_index]);
+        if len == 0 {
+            return &mut [];
+        }
+
+        std::slice::from_raw_parts_mut(ptr as *mut u8, len)
+    }
+
+    fn column_raw(&self, component_index: usize) -> (*const u8, usize) {
+        let size = unsafe {
+            *self
+                .page_view
+                .archetype
+                .component_sizes()
+                .add(component_index)
+        };
+        let ptr = self.page_view.get_component_data_ptr(component_index, 0);
+
+        (ptr, size * self.len())
+    }
+}
+
+impl Store {
+    /// Adds the component of the runtime type with the bytes of its value.
+    /// The previous value of the component is dropped.
+    ///
+    /// # Safety
+    /// The bytes must be the valid value of the component type. The store owns
+    /// the value after the call and drops it with the drop glue of the type
+    pub unsafe fn add_component_bytes(
+        &mut self,
+        entity: Entity,
+        component_type: &ComponentType,
+        bytes: &[u8],
+    ) {
+        assert_eq!(
+            bytes.len(),
+            component_type.size(),
+            "Bytes length doesn't match the size of component {}",
+            component_type.name()
+        );
+
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let tick = self.increment_change_tick();
+        let type_id = component_type.id();
+        let mut previous_arch_index = None;
+
+        let entity_in_arch = if self.has_archetype_unchecked(entity.id) {
+            let entity_in_arch = self.get_entity_in_archetype_unchecked(entity.id);
+            let arch_index = self
+                .archetypes_container
+                .get_archetype_index_by_page(entity_in_arch.page_index as usize);
+            let page_view = self
+                .archetypes_container
+                .get_page_view_unchecked(entity_in_arch.page_index as usize);
+
+            if let Some(component_index) =
+                page_view.archetype.find_component_index(type_id)
+            {
+                let ptr = page_view.get_component_data_ptr(
+                    component_index,
+                    entity_in_arch.index_in_page as usize,
+                ) as *mut u8;
+                if let Some(drop_fn) = component_type.drop_fn() {
+                    drop_fn(ptr);
+                }
+                bytes.as_ptr().copy_to_nonoverlapping(ptr, bytes.len());
+                page_view.page.mark_component_changed(component_index, tick);
+
+                return;
+            }
+
+            let new_arch_index = self
+                .archetypes_container
+                .reserve_archetype_with_added_type(arch_index, component_type);
+            previous_arch_index = Some(arch_index);
+
+            self.move_entity_to_other_archetype(entity, new_arch_index, tick)
+        } else {
+            let archetype = ArchetypeBuilder::new()
+                .include_component_types(&[*component_type])
+                .build();
+            self.attach_entity_to_archetype(entity.id, &archetype)
+        };
+
+        let page_view = self
+            .archetypes_container
+            .get_page_view_unchecked(entity_in_arch.page_index as usize);
+        let component_index = page_view.archetype.find_component_index(type_id).unwrap();
+        let ptr = page_view.get_component_data_ptr(
+            component_index,
+            entity_in_arch.index_in_page as usize,
+        );
+        bytes
+            .as_ptr()
+            .copy_to_nonoverlapping(ptr as *mut u8, bytes.len());
+
+        if self.lifecycle_hooks.has_component_hooks() {
+            self.call_on_add_hooks(entity, &[type_id], previous_arch_index);
+        }
+    }
+
+    /// Removes and drops the component of the runtime type
+    pub fn remove_component_type(
+        &mut self,
+        entity: Entity,
+        component_type: &ComponentType,
+    ) {
+        if !self.is_valid_entity_with_archetype(entity) {
+            return;
+        }
+
+        let entity_in_arch = unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
+        let arch_index = self
+            .archetypes_container
+            .get_archetype_index_by_page(entity_in_arch.page_index as usize);
+
+        let new_arch_index = self
+            .archetypes_container
+            .reserve_archetype_with_removed_type(arch_index, component_type);
+        if new_arch_index == arch_index {
+            return;
+        }
+
+        if self.lifecycle_hooks.has_component_hooks() {
+            self.call_on_remove_hooks(entity, &[component_type.id()]);
+        }
+
+        let tick = self.increment_change_tick();
+        self.move_entity_to_other_archetype(entity, new_arch_index, tick);
+    }
+
+    /// Bytes of the entity component. `None`, if the entity doesn't have it.
+    /// Panics, if the type isn't the runtime type of [`ComponentType::dynamic`],
+    /// since the bytes of the Rust type can contain uninitialized padding
+    pub fn get_component_bytes(
+        &self,
+        entity: Entity,
+        component_type: &ComponentType,
+    ) -> Option<&[u8]> {
+        component_type.assert_dynamic();
+        let (ptr, len) = self.get_component_bytes_ptr(entity, component_type)?;
+        if len == 0 {
+            return Some(&[]);
+        }
+
+        Some(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+
+    /// Mutable bytes of the entity component, which is marked as changed.
+    /// Panics, if the type isn't the runtime type of [`ComponentType::dynamic`]
+    ///
+    /// # Safety
+    /// The bytes must stay the valid value of the component type, e.g. the handle,
+    /// which the drop glue releases, must not be overwritten with the garbage
+    pub unsafe fn get_component_bytes_mut(
+        &mut self,
+        entity: Entity,
+        component_type: &ComponentType,
+    ) -> Option<&mut [u8]> {
+        component_type.assert_dynamic();
+        let (ptr, len) = self.get_component_bytes_ptr(entity, component_type)?;
+
+        let tick = self.increment_change_tick();
+        let (page_view, _) = self.get_page_info(entity.id);
+        page_view.page.mark_components_changed(
+            page_view.archetype,
+            &[component_type.id()],
+            tick,
+        );
+
+        if len == 0 {
+            return Some(&mut []);
+        }
+
+        Some(std::slice::from_raw_parts_mut(ptr as *mut u8, len))
+    }
+
+    fn get_component_bytes_ptr(
+        &self,
+        entity: Entity,
+        component_type: &ComponentType,
+    ) -> Option<(*const u8, usize)> {
+        if !self.is_valid_entity_with_archetype(entity) {
+            return None;
+        }
+
+        let (page_view, index_in_page) = unsafe { self.get_page_info(entity.id) };
+        let component_index = page_view
+            .archetype
+            .find_component_index(component_type.id())?;
+
+        Some((
+            page_view.get_component_data_ptr(component_index, index_in_page),
+            component_type.size(),
+        ))
+    }
+
+    /// Iterates the pages with the entities, matched by the query
+    pub fn dynamic_query_iter<'a>(
+        &'a self,
+        query: &'a mut DynamicQuery,
+    ) -> DynamicQueryIter<'a> {
+        query.update_matched_archetypes(&self.archetypes_container);
+
+        DynamicQueryIter {
+            store: self,
+            query,
+            tick: self.increment_change_tick(),
+            matched_index: 0,
+            page_index: 0,
+        }
+    }
+}
@@ -63,6 +63,12 @@ impl EntitiesContainer {
         self.capacity = new_capacity;
     }
 
+    /// Grows the container once to fit `additional` new entities
+    pub fn reserve(&mut self, additional: usize) {
+        let new_ids_count = additional.saturating_sub(self.gap_ids.len());
+        self.grow(self.next_free_id as usize + new_ids_count);
+    }
+
     #[inline(always)]
     fn will_grow_with_id(&self, id: u32) -> bool {
         id >= self.capacity as u32
@@ -134,6 +140,32 @@ impl EntitiesContainer {
         unsafe { bitvec_utils::is_bit_on(self.entity_to_is_alive_bitvec, index) }
     }
 
+    /// Replaces state of the empty container with the versions of the used ids
+    /// and the alive ids subset. Capacity must fit all the versions
+    #[cfg(feature = "serde")]
+    pub(crate) fn restore(&mut self, versions: &[u32], alive_ids: &[u32]) {
+        debug_assert!(self.next_free_id == 0, "Container must be empty");
+        debug_assert!(versions.len() <= self.capacity);
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                versions.as_ptr(),
+                self.entity_to_version,
+                versions.len(),
+            );
+
+            for &id in alive_ids {
+                bitvec_utils::set_bit_on(self.entity_to_is_alive_bitvec, id as usize);
+            }
+        }
+
+        self.next_free_id = versions.len() as u32;
+        self.gap_ids = (0..self.next_free_id)
+            .rev()
+            .filter(|&id| !self.is_alive_at_index(id as usize))
+            .collect();
+    }
+
     #[inline(always)]
     pub(crate) fn entity_versions(&self) -> *const u32 {
         self.entity_to_version
@@ -165,6 +197,32 @@ impl EntitiesContainer {
     }
 }
 
+impl Clone for EntitiesContainer {
+    fn clone(&self) -> Self {
+        let (entity_to_version, entity_to_is_alive_bitvec) = unsafe {
+            let versions = mem_utils::alloc(self.capacity);
+            let is_alive_bitvec = bitvec_utils::alloc(self.capacity);
+
+            self.entity_to_version
+                .copy_to_nonoverlapping(versions, self.capacity);
+            self.entity_to_is_alive_bitvec.copy_to_nonoverlapping(
+                is_alive_bitvec,
+                bitvec_utils::get_len(self.capacity),
+            );
+
+            (versions, is_alive_bitvec)
+        };
+
+        Self {
+            gap_ids: self.gap_ids.clone(),
+            capacity: self.capacity,
+            entity_to_version,
+            entity_to_is_alive_bitvec,
+            next_free_id: self.next_free_id,
+        }
+    }
+}
+
 impl Drop for EntitiesContainer {
     fn drop(&mut self) {
         unsafe {
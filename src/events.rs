@@ -0,0 +1,243 @@
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
+
+use crate::Store;
+
+/// Double-buffered queue of the events. Events are kept for two [`Events::update`]
+/// calls, so every reader, which reads once per update, sees each event exactly once
+pub struct Events<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+    /// Id of the first event in `previous`
+    previous_start_id: usize,
+    /// Id of the next sent event
+    next_id: usize,
+}
+
+/// Cursor of the single reader. Each reader keeps its own position in the queue
+pub struct EventReader<T> {
+    next_id: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+pub type EventsIter<'a, T> =
+    std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+/// Type-erased events queue, which is updated by the store
+trait EventsQueue {
+    fn update(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub(crate) struct EventsContainer {
+    type_ids: Vec<TypeId>,
+    queues: Vec<Box<dyn EventsQueue>>,
+}
+
+impl<T> Events<T> {
+    pub fn new() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start_id: 0,
+            next_id: 0,
+        }
+    }
+
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+        self.next_id += 1;
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        for event in events {
+            self.send(event);
+        }
+    }
+
+    /// Swaps the buffers. Events of the previous update are dropped
+    pub fn update(&mut self) {
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+        self.previous_start_id = self.next_id - self.previous.len();
+    }
+
+    /// Reader, which reads all events in the queue
+    pub fn reader(&self) -> EventReader<T> {
+        EventReader {
+            next_id: self.previous_start_id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reader, which skips events sent before its creation
+    pub fn reader_from_now(&self) -> EventReader<T> {
+        EventReader {
+            next_id: self.next_id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Iterates events, which are not read by the reader yet, and moves its cursor
+    /// to the end. Expired events, which the reader missed, are skipped
+    pub fn read<'a>(&'a self, reader: &mut EventReader<T>) -> EventsIter<'a, T> {
+        let current_start_id = self.previous_start_id + self.previous.len();
+        let next_id = reader.next_id.max(self.previous_start_id);
+
+        let previous_start = (next_id - self.previous_start_id).min(self.previous.len());
+        let current_start = next_id
+            .saturating_sub(current_start_id)
+            .min(self.current.len());
+        reader.next_id = self.next_id;
+
+        self.previous[previous_start..]
+            .iter()
+            .chain(self.current[current_start..].iter())
+    }
+
+    /// Count of the events in both buffers
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all events. Readers continue with the events sent after the call
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+        self.previous_start_id = self.next_id;
+    }
+
+    /// Takes all events out of the queue in the sending order
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.previous_start_id = self.next_id;
+        self.previous.drain(..).chain(self.current.drain(..))
+    }
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> EventReader<T> {
+    /// Count of the events in the queue, which are not read yet
+    pub fn unread_count(&self, events: &Events<T>) -> usize {
+        events.next_id - self.next_id.max(events.previous_start_id)
+    }
+}
+
+impl<T> Clone for EventReader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            next_id: self.next_id,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> EventsQueue for Events<T> {
+    fn update(&mut self) {
+        Events::update(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl EventsContainer {
+    pub fn new() -> Self {
+        Self {
+            type_ids: Vec::new(),
+            queues: Vec::new(),
+        }
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&Events<T>> {
+        self.type_ids
+            .binary_search(&TypeId::of::<T>())
+            .ok()
+            .map(|i| unsafe { self.queues[i].as_any().downcast_ref().unwrap_unchecked() })
+    }
+
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut Events<T>> {
+        self.type_ids
+            .binary_search(&TypeId::of::<T>())
+            .ok()
+            .map(|i| unsafe {
+                self.queues[i]
+                    .as_any_mut()
+                    .downcast_mut()
+                    .unwrap_unchecked()
+            })
+    }
+
+    pub fn get_or_add<T: 'static>(&mut self) -> &mut Events<T> {
+        let type_id = TypeId::of::<T>();
+        let index = match self.type_ids.binary_search(&type_id) {
+            Ok(i) => i,
+            Err(i) => {
+                self.type_ids.insert(i, type_id);
+                self.queues.insert(i, Box::new(Events::<T>::new()));
+                i
+            }
+        };
+
+        unsafe {
+            self.queues[index]
+                .as_any_mut()
+                .downcast_mut()
+                .unwrap_unchecked()
+        }
+    }
+
+    pub fn update(&mut self) {
+        for queue in &mut self.queues {
+            queue.update();
+        }
+    }
+}
+
+impl Store {
+    /// Registers the events queue of the type, so it's updated with
+    /// [`Store::update_events`] before any event is sent
+    pub fn add_events<T: Send + Sync + 'static>(&mut self) {
+        self.events_container.get_or_add::<T>();
+    }
+
+    pub fn send_event<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.events_container.get_or_add::<T>().send(event);
+    }
+
+    /// Events are read by the systems, which share the store between threads,
+    /// so the events types must be `Send + Sync`
+    #[inline]
+    pub fn events<T: Send + Sync + 'static>(&self) -> Option<&Events<T>> {
+        self.events_container.get::<T>()
+    }
+
+    #[inline]
+    pub fn events_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut Events<T>> {
+        self.events_container.get_mut::<T>()
+    }
+
+    /// Updates all events queues of the store. It's expected to be called
+    /// once per frame
+    pub fn update_events(&mut self) {
+        self.events_container.update();
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{mem_utils, Entity, Store};
+
+/// Parent/child links of the entities. Children of the same parent are kept in the
+/// doubly linked list, so the hierarchy is changed without allocations
+pub(crate) struct HierarchyContainer {
+    id_to_parent: *mut u32,
+    id_to_first_child: *mut u32,
+    id_to_last_child: *mut u32,
+    id_to_next_sibling: *mut u32,
+    id_to_previous_sibling: *mut u32,
+    entity_capacity: usize,
+}
+
+/// Iterator over the direct children of the entity
+pub struct ChildrenIter<'a> {
+    store: &'a Store,
+    next_id: u32,
+}
+
+/// Depth-first (pre-order) iterator over the entity and all of its descendants
+pub struct HierarchyIter<'a> {
+    store: &'a Store,
+    root_id: u32,
+    next_id: u32,
+}
+
+impl HierarchyContainer {
+    const NULL_ID_BYTE: u8 = Entity::NULL_ID as u8;
+
+    pub fn new(entity_capacity: usize) -> Self {
+        unsafe {
+            Self {
+                id_to_parent: Self::alloc_links_map(entity_capacity),
+                id_to_first_child: Self::alloc_links_map(entity_capacity),
+                id_to_last_child: Self::alloc_links_map(entity_capacity),
+                id_to_next_sibling: Self::alloc_links_map(entity_capacity),
+                id_to_previous_sibling: Self::alloc_links_map(entity_capacity),
+                entity_capacity,
+            }
+        }
+    }
+
+    pub fn set_parent(&mut self, child_id: u32, parent_id: u32) {
+        assert!(
+            !self.is_descendant_or_self(parent_id, child_id),
+            "Entity {parent_id} can't be a parent of its ancestor {child_id}"
+        );
+
+        self.remove_parent(child_id);
+
+        unsafe {
+            let last_child = *self.id_to_last_child.add(parent_id as usize);
+            if last_child == Entity::NULL_ID {
+                *self.id_to_first_child.add(parent_id as usize) = child_id;
+            } else {
+                *self.id_to_next_sibling.add(last_child as usize) = child_id;
+                *self.id_to_previous_sibling.add(child_id as usize) = last_child;
+            }
+
+            *self.id_to_last_child.add(parent_id as usize) = child_id;
+            *self.id_to_parent.add(child_id as usize) = parent_id;
+        }
+    }
+
+    pub fn remove_parent(&mut self, child_id: u32) {
+        unsafe {
+            let parent = *self.id_to_parent.add(child_id as usize);
+            if parent == Entity::NULL_ID {
+                return;
+            }
+
+            let next = &mut *self.id_to_next_sibling.add(child_id as usize);
+            let previous = &mut *self.id_to_previous_sibling.add(child_id as usize);
+
+            if *next == Entity::NULL_ID {
+                *self.id_to_last_child.add(parent as usize) = *previous;
+            } else {
+                *self.id_to_previous_sibling.add(*next as usize) = *previous;
+            }
+
+            if *previous == Entity::NULL_ID {
+                *self.id_to_first_child.add(parent as usize) = *next;
+            } else {
+                *self.id_to_next_sibling.add(*previous as usize) = *next;
+            }
+
+            *next = Entity::NULL_ID;
+            *previous = Entity::NULL_ID;
+            *self.id_to_parent.add(child_id as usize) = Entity::NULL_ID;
+        }
+    }
+
+    #[inline]
+    pub fn get_parent_id(&self, id: u32) -> Option<u32> {
+        Self::get_link(self.id_to_parent, id)
+    }
+
+    #[inline]
+    pub fn get_first_child_id(&self, id: u32) -> Option<u32> {
+        Self::get_link(self.id_to_first_child, id)
+    }
+
+    #[inline]
+    pub fn get_next_sibling_id(&self, id: u32) -> Option<u32> {
+        Self::get_link(self.id_to_next_sibling, id)
+    }
+
+    /// Next id after `id` in the depth-first traversal of the `root_id` subtree
+    pub fn get_next_id_depth_first(&self, root_id: u32, id: u32) -> Option<u32> {
+        if let Some(child) = self.get_first_child_id(id) {
+            return Some(child);
+        }
+
+        let mut current = id;
+        while current != root_id {
+            if let Some(sibling) = self.get_next_sibling_id(current) {
+                return Some(sibling);
+            }
+
+            current = self.get_parent_id(current)?;
+        }
+
+        None
+    }
+
+    /// Ids of all descendants of the entity in the depth-first order
+    pub fn collect_descendant_ids(&self, id: u32) -> Vec<u32> {
+        let mut ids = Vec::new();
+        let mut current = self.get_next_id_depth_first(id, id);
+        while let Some(descendant) = current {
+            ids.push(descendant);
+            current = self.get_next_id_depth_first(id, descendant);
+        }
+
+        ids
+    }
+
+    pub fn grow(&mut self, new_capacity: usize) {
+        let old_capacity = self.entity_capacity;
+        let delta_capacity = new_capacity - old_capacity;
+
+        for links in [
+            &mut self.id_to_parent,
+            &mut self.id_to_first_child,
+            &mut self.id_to_last_child,
+            &mut self.id_to_next_sibling,
+            &mut self.id_to_previous_sibling,
+        ] {
+            unsafe {
+                *links = mem_utils::realloc(*links, old_capacity, new_capacity);
+                links
+                    .add(old_capacity)
+                    .write_bytes(Self::NULL_ID_BYTE, delta_capacity);
+            }
+        }
+
+        self.entity_capacity = new_capacity;
+    }
+
+    pub fn is_descendant_or_self(&self, id: u32, ancestor_id: u32) -> bool {
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            if current_id == ancestor_id {
+                return true;
+            }
+
+            current = self.get_parent_id(current_id);
+        }
+
+        false
+    }
+
+    #[inline]
+    fn get_link(links: *const u32, id: u32) -> Option<u32> {
+        let target_id = unsafe { *links.add(id as usize) };
+        if target_id != Entity::NULL_ID {
+            Some(target_id)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn alloc_links_map(entity_capacity: usize) -> *mut u32 {
+        let links = mem_utils::alloc::<u32>(entity_capacity);
+        links.write_bytes(Self::NULL_ID_BYTE, entity_capacity);
+        links
+    }
+}
+
+impl Store {
+    /// Makes `child` the last child of `parent`. Previous parent of `child` is replaced.
+    /// Panics, if `parent` is `child` itself or its descendant
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        assert!(self.is_alive(child) && self.is_alive(parent));
+        self.hierarchy_container.set_parent(child.id, parent.id);
+    }
+
+    pub fn remove_parent(&mut self, child: Entity) {
+        if self.is_alive(child) {
+            self.hierarchy_container.remove_parent(child.id);
+        }
+    }
+
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+
+        self.hierarchy_container
+            .get_parent_id(entity.id)
+            .map(|id| unsafe { self.get_entity_by_id_unchecked(id) })
+    }
+
+    pub fn children(&self, entity: Entity) -> ChildrenIter<'_> {
+        let next_id = if self.is_alive(entity) {
+            self.hierarchy_container
+                .get_first_child_id(entity.id)
+                .unwrap_or(Entity::NULL_ID)
+        } else {
+            Entity::NULL_ID
+        };
+
+        ChildrenIter {
+            store: self,
+            next_id,
+        }
+    }
+
+    /// Iterates the entity and its descendants depth-first: every parent goes before
+    /// its children, children go in the order of [`Store::set_parent`] calls
+    pub fn hierarchy_iter(&self, root: Entity) -> HierarchyIter<'_> {
+        let next_id = if self.is_alive(root) {
+            root.id
+        } else {
+            Entity::NULL_ID
+        };
+
+        HierarchyIter {
+            store: self,
+            root_id: root.id,
+            next_id,
+        }
+    }
+}
+
+impl<'a> Iterator for ChildrenIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_id == Entity::NULL_ID {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id = self
+            .store
+            .hierarchy_container
+            .get_next_sibling_id(id)
+            .unwrap_or(Entity::NULL_ID);
+
+        Some(unsafe { self.store.get_entity_by_id_unchecked(id) })
+    }
+}
+
+impl<'a> Iterator for HierarchyIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_id == Entity::NULL_ID {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id = self
+            .store
+            .hierarchy_container
+            .get_next_id_depth_first(self.root_id, id)
+            .unwrap_or(Entity::NULL_ID);
+
+        Some(unsafe { self.store.get_entity_by_id_unchecked(id) })
+    }
+}
+
+impl Clone for HierarchyContainer {
+    fn clone(&self) -> Self {
+        let clone_links = |links: *mut u32| unsafe {
+            let cloned = mem_utils::alloc(self.entity_capacity);
+            links.copy_to_nonoverlapping(cloned, self.entity_capacity);
+            cloned
+        };
+
+        Self {
+            id_to_parent: clone_links(self.id_to_parent),
+            id_to_first_child: clone_links(self.id_to_first_child),
+            id_to_last_child: clone_links(self.id_to_last_child),
+            id_to_next_sibling: clone_links(self.id_to_next_sibling),
+            id_to_previous_sibling: clone_links(self.id_to_previous_sibling),
+            entity_capacity: self.entity_capacity,
+        }
+    }
+}
+
+impl Drop for HierarchyContainer {
+    fn drop(&mut self) {
+        for links in [
+            self.id_to_parent,
+            self.id_to_first_child,
+            self.id_to_last_child,
+            self.id_to_next_sibling,
+            self.id_to_previous_sibling,
+        ] {
+            unsafe {
+                mem_utils::dealloc(links, self.entity_capacity);
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+// Lets the derived impls refer to `::division_ecs` inside this crate too
+extern crate self as division_ecs;
+
 mod archetype;
 
 mod archetype_builder;
@@ -7,17 +10,32 @@ mod archetype_data_page_view;
 mod archetype_layout;
 mod archetypes_container;
 mod bitvec_utils;
+mod command_buffer;
+mod component_clone_fns;
 mod component_tuple;
 mod component_type;
 mod derived_traits;
+mod dynamic_component;
 mod entities_container;
 mod entity;
 mod entity_in_archetype;
+mod events;
+mod hierarchy_container;
+mod lifecycle_hooks;
 mod mem_utils;
 mod order_group_container;
+mod prefab;
+mod reflection;
 mod resource_store;
+mod resources_container;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serialization;
 mod store;
+mod store_config;
 mod tag_container;
+mod tag_tuple;
+mod tick_utils;
 mod tests;
 
 pub mod macros;
@@ -25,16 +43,33 @@ pub mod query;
 
 pub use archetype::Archetype;
 pub use archetype_builder::ArchetypeBuilder;
-pub use component_type::ComponentType;
+pub use command_buffer::CommandBuffer;
+pub use component_type::{ComponentCloneFn, ComponentId, ComponentType};
 pub use derived_traits::{Component, Tag};
+pub use dynamic_component::{DynamicQuery, DynamicQueryIter, DynamicQueryPage};
 
 pub use entity::Entity;
-pub use resource_store::ResourceStore;
+pub use events::{EventReader, Events, EventsIter};
+pub use hierarchy_container::{ChildrenIter, HierarchyIter};
+pub use prefab::Prefab;
+pub use reflection::{ComponentReflection, FieldInfo, Reflect};
+pub use resource_store::{
+    ResourceStore, ResourceStoreDrain, ResourceStoreIter, ResourceStoreIterMut,
+};
+pub use resources_container::{ResourceMut, ResourceRef};
+pub use schedule::{Schedule, SystemAccess, SystemContext, SystemId};
+#[cfg(feature = "serde")]
+pub use serialization::{SerializableStore, SerializationRegistry, StoreSeed};
 pub use store::Store;
+pub use store_config::StoreConfig;
+pub use tag_container::TagQueryIter;
+pub use tag_tuple::TagTuple;
 
 pub use component_tuple::{ClonedExtension, ComponentTuple};
 
-pub use query::access::{ReadWriteAccess, ReadonlyAccess, WriteAccess};
+pub use query::access::{
+    Optional, OptionalMut, ReadWriteAccess, ReadonlyAccess, With, Without, WriteAccess,
+};
 pub use query::component::{
     ComponentQuery, ComponentReadOnlyQuery, ComponentReadWriteQuery, ComponentWriteQuery,
 };
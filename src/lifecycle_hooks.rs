@@ -0,0 +1,247 @@
+use std::any::TypeId;
+
+use crate::{Archetype, Component, ComponentId, Entity, Store, Tag};
+
+type ComponentHook = Box<dyn FnMut(Entity, *const u8)>;
+type EntityHook = Box<dyn FnMut(Entity)>;
+
+/// Callbacks on the entities and components lifecycle events. Component hooks are
+/// type-erased and called with the pointer to the component of the hook type
+#[derive(Default)]
+pub(crate) struct LifecycleHooks {
+    on_add: Vec<(ComponentId, ComponentHook)>,
+    on_remove: Vec<(ComponentId, ComponentHook)>,
+    on_entity_destroyed: Vec<EntityHook>,
+    on_tag_added: Vec<(TypeId, EntityHook)>,
+    on_tag_removed: Vec<(TypeId, EntityHook)>,
+}
+
+impl LifecycleHooks {
+    #[inline(always)]
+    pub fn has_component_hooks(&self) -> bool {
+        !self.on_add.is_empty() || !self.on_remove.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn has_destroy_hooks(&self) -> bool {
+        self.has_component_hooks()
+            || !self.on_entity_destroyed.is_empty()
+            || !self.on_tag_removed.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn has_tag_hooks(&self) -> bool {
+        !self.on_tag_added.is_empty() || !self.on_tag_removed.is_empty()
+    }
+
+    pub fn has_on_add_hook(&self, type_id: ComponentId) -> bool {
+        self.on_add.iter().any(|(id, _)| *id == type_id)
+    }
+
+    pub fn has_on_remove_hook(&self, type_id: ComponentId) -> bool {
+        self.on_remove.iter().any(|(id, _)| *id == type_id)
+    }
+
+    /// # Safety
+    /// `component` must point to the initialized component with the type id
+    pub unsafe fn call_on_add(
+        &mut self,
+        type_id: ComponentId,
+        entity: Entity,
+        component: *const u8,
+    ) {
+        Self::call_component_hooks(&mut self.on_add, type_id, entity, component);
+    }
+
+    /// # Safety
+    /// `component` must point to the initialized component with the type id
+    pub unsafe fn call_on_remove(
+        &mut self,
+        type_id: ComponentId,
+        entity: Entity,
+        component: *const u8,
+    ) {
+        Self::call_component_hooks(&mut self.on_remove, type_id, entity, component);
+    }
+
+    pub fn call_on_entity_destroyed(&mut self, entity: Entity) {
+        for hook in &mut self.on_entity_destroyed {
+            hook(entity);
+        }
+    }
+
+    pub fn call_on_tag_added(&mut self, type_id: TypeId, entity: Entity) {
+        Self::call_entity_hooks(&mut self.on_tag_added, type_id, entity);
+    }
+
+    pub fn call_on_tag_removed(&mut self, type_id: TypeId, entity: Entity) {
+        Self::call_entity_hooks(&mut self.on_tag_removed, type_id, entity);
+    }
+
+    pub fn tag_removed_hook_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.on_tag_removed.iter().map(|(id, _)| *id)
+    }
+
+    unsafe fn call_component_hooks(
+        hooks: &mut [(ComponentId, ComponentHook)],
+        type_id: ComponentId,
+        entity: Entity,
+        component: *const u8,
+    ) {
+        for (id, hook) in hooks {
+            if *id == type_id {
+                hook(entity, component);
+            }
+        }
+    }
+
+    fn call_entity_hooks(
+        hooks: &mut [(TypeId, EntityHook)],
+        type_id: TypeId,
+        entity: Entity,
+    ) {
+        for (id, hook) in hooks {
+            if *id == type_id {
+                hook(entity);
+            }
+        }
+    }
+}
+
+impl Store {
+    /// Registers the callback, which is called after the component is added to the entity.
+    /// It's not called, when the existing component value is replaced, and for the
+    /// uninitialized components of [`Store::create_entity_with_archetype`]
+    pub fn on_add<T: Component + 'static>(
+        &mut self,
+        mut hook: impl FnMut(Entity, &T) + 'static,
+    ) {
+        self.lifecycle_hooks.on_add.push((
+            ComponentId::of::<T>(),
+            Box::new(move |entity, ptr| hook(entity, unsafe { &*(ptr as *const T) })),
+        ));
+    }
+
+    /// Registers the callback, which is called before the component is removed from
+    /// the entity or the entity is destroyed
+    pub fn on_remove<T: Component + 'static>(
+        &mut self,
+        mut hook: impl FnMut(Entity, &T) + 'static,
+    ) {
+        self.lifecycle_hooks.on_remove.push((
+            ComponentId::of::<T>(),
+            Box::new(move |entity, ptr| hook(entity, unsafe { &*(ptr as *const T) })),
+        ));
+    }
+
+    /// Registers the callback, which is called before the entity is destroyed.
+    /// Descendants in the hierarchy are destroyed before the ancestors
+    pub fn on_entity_destroyed(&mut self, hook: impl FnMut(Entity) + 'static) {
+        self.lifecycle_hooks
+            .on_entity_destroyed
+            .push(Box::new(hook));
+    }
+
+    pub fn on_tag_added<T: Tag>(&mut self, hook: impl FnMut(Entity) + 'static) {
+        self.lifecycle_hooks
+            .on_tag_added
+            .push((TypeId::of::<T>(), Box::new(hook)));
+    }
+
+    /// Registers the callback, which is called when the tag is removed from the entity
+    /// or the entity with the tag is destroyed
+    pub fn on_tag_removed<T: Tag>(&mut self, hook: impl FnMut(Entity) + 'static) {
+        self.lifecycle_hooks
+            .on_tag_removed
+            .push((TypeId::of::<T>(), Box::new(hook)));
+    }
+
+    /// Calls `on_add` hooks for the components of the entity, which are not included
+    /// in the previous archetype
+    pub(crate) fn call_on_add_hooks(
+        &mut self,
+        entity: Entity,
+        type_ids: &[ComponentId],
+        previous_archetype_index: Option<usize>,
+    ) {
+        let previous_archetype: Option<&Archetype> = previous_archetype_index
+            .map(|index| &self.archetypes_container.get_archetypes()[index]);
+        let added_ids: Vec<_> = type_ids
+            .iter()
+            .copied()
+            .filter(|&type_id| {
+                self.lifecycle_hooks.has_on_add_hook(type_id)
+                    && previous_archetype
+                        .is_none_or(|arch| arch.find_component_index(type_id).is_none())
+            })
+            .collect();
+
+        for (type_id, ptr) in self.get_component_ptrs(entity, &added_ids) {
+            unsafe { self.lifecycle_hooks.call_on_add(type_id, entity, ptr) };
+        }
+    }
+
+    /// Calls `on_remove` hooks for the entity components with the type ids
+    pub(crate) fn call_on_remove_hooks(
+        &mut self,
+        entity: Entity,
+        type_ids: &[ComponentId],
+    ) {
+        let removed_ids: Vec<_> = type_ids
+            .iter()
+            .copied()
+            .filter(|&type_id| self.lifecycle_hooks.has_on_remove_hook(type_id))
+            .collect();
+
+        for (type_id, ptr) in self.get_component_ptrs(entity, &removed_ids) {
+            unsafe { self.lifecycle_hooks.call_on_remove(type_id, entity, ptr) };
+        }
+    }
+
+    pub(crate) fn call_destroy_hooks(&mut self, entity: Entity) {
+        if unsafe { self.has_archetype_unchecked(entity.id) } {
+            let type_ids = {
+                let (page_view, _) = unsafe { self.get_page_info(entity.id) };
+                page_view.archetype.included_ids().to_vec()
+            };
+            self.call_on_remove_hooks(entity, &type_ids);
+        }
+
+        let removed_tag_ids: Vec<_> = self
+            .lifecycle_hooks
+            .tag_removed_hook_ids()
+            .filter(|&type_id| self.tag_container.has_tag_by_id(type_id, entity.id))
+            .collect();
+        for type_id in removed_tag_ids {
+            self.lifecycle_hooks.call_on_tag_removed(type_id, entity);
+        }
+
+        self.lifecycle_hooks.call_on_entity_destroyed(entity);
+    }
+
+    /// Pointers to the components of the entity, which archetype includes the type ids
+    fn get_component_ptrs(
+        &self,
+        entity: Entity,
+        type_ids: &[ComponentId],
+    ) -> Vec<(ComponentId, *const u8)> {
+        if type_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let (page_view, index_in_page) = unsafe { self.get_page_info(entity.id) };
+        type_ids
+            .iter()
+            .filter_map(|&type_id| {
+                page_view
+                    .archetype
+                    .find_component_index(type_id)
+                    .map(|component_index| {
+                        let ptr = page_view
+                            .get_component_data_ptr(component_index, index_in_page);
+                        (type_id, ptr)
+                    })
+            })
+            .collect()
+    }
+}
@@ -5,6 +5,13 @@ macro_rules! component_types {
     };
 }
 
+#[macro_export]
+macro_rules! component_ids {
+    ($($T:ident), *) => {
+        [$($crate::ComponentId::of::<$T>()),*]
+    };
+}
+
 #[macro_export]
 macro_rules! type_ids {
     ($($T:ident), *) => {
@@ -64,7 +64,24 @@ impl OrderGroupContainer {
     #[inline]
     pub fn add_id_next_to_ordered_by<T: Tag>(&mut self, entity_id: u32, next_to_id: u32) {
         let group_index = self.reserve_group::<T>();
+        self.add_id_next_to_in_group(group_index, entity_id, next_to_id);
+    }
+
+    /// Places the entity right after `next_to_id` in every group, that contains it
+    pub fn add_id_next_to_in_groups_of(&mut self, entity_id: u32, next_to_id: u32) {
+        for group_index in 0..self.group_ids.len() {
+            if self.is_id_in_group(group_index, next_to_id) {
+                self.add_id_next_to_in_group(group_index, entity_id, next_to_id);
+            }
+        }
+    }
 
+    fn add_id_next_to_in_group(
+        &mut self,
+        group_index: usize,
+        entity_id: u32,
+        next_to_id: u32,
+    ) {
         let OrderGroupInfoMut {
             backward_links,
             forward_links,
@@ -93,6 +110,17 @@ impl OrderGroupContainer {
         }
     }
 
+    fn is_id_in_group(&self, group_index: usize, entity_id: u32) -> bool {
+        unsafe {
+            *self.group_index_to_head.get_unchecked(group_index) == entity_id
+                || *self.group_index_to_forward_links[group_index].add(entity_id as usize)
+                    != Entity::NULL_ID
+                || *self.group_index_to_backward_links[group_index]
+                    .add(entity_id as usize)
+                    != Entity::NULL_ID
+        }
+    }
+
     #[inline]
     pub fn add_id_previous_to_ordered_by<T: Tag>(
         &mut self,
@@ -395,6 +423,32 @@ impl Store {
     }
 }
 
+impl Clone for OrderGroupContainer {
+    fn clone(&self) -> Self {
+        let clone_links = |links: &Vec<*mut u32>| -> Vec<*mut u32> {
+            links
+                .iter()
+                .map(|&links_map| unsafe {
+                    let cloned = mem_utils::alloc(self.entity_capacity);
+                    links_map.copy_to_nonoverlapping(cloned, self.entity_capacity);
+                    cloned
+                })
+                .collect()
+        };
+
+        Self {
+            group_ids: self.group_ids.clone(),
+            group_index_to_forward_links: clone_links(&self.group_index_to_forward_links),
+            group_index_to_backward_links: clone_links(
+                &self.group_index_to_backward_links,
+            ),
+            group_index_to_head: self.group_index_to_head.clone(),
+            group_index_to_tail: self.group_index_to_tail.clone(),
+            entity_capacity: self.entity_capacity,
+        }
+    }
+}
+
 impl Drop for OrderGroupContainer {
     fn drop(&mut self) {
         for (&bwd_links, &fwd_links) in std::iter::zip(
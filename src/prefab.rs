@@ -0,0 +1,180 @@
+use std::any::TypeId;
+
+use crate::{
+    archetype_data_page::ArchetypeDataPage,
+    archetype_data_page_view::ArchetypeDataPageView, archetype_layout::ArchetypeLayout,
+    component_tuple::ComponentTuple, Archetype, ArchetypeBuilder, ComponentType, Entity,
+    Store, Tag,
+};
+
+/// Template of the entity: the archetype with the prototype components and the tags.
+/// The prototype is cloned into every instance by [`Store::instantiate`]
+#[derive(Debug)]
+pub struct Prefab {
+    archetype: Archetype,
+    layout: ArchetypeLayout,
+    /// Page with the single prototype entity
+    row: ArchetypeDataPage,
+    tag_ids: Vec<TypeId>,
+}
+
+impl Prefab {
+    pub fn new<T: ComponentTuple>(components: T) -> Self {
+        let archetype = Archetype::with_components::<T>();
+        let (layout, row) = Self::new_row(&archetype);
+
+        unsafe {
+            let offsets = T::get_offsets_unchecked(&archetype, &layout);
+            T::write_to_ptrs(T::get_ptrs_mut(&row, &offsets), components);
+        }
+
+        Self {
+            archetype,
+            layout,
+            row,
+            tag_ids: Vec::new(),
+        }
+    }
+
+    /// Adds the tag to every instance of the prefab
+    pub fn with_tag<T: Tag>(mut self) -> Self {
+        let type_id = TypeId::of::<T>();
+        if self.tag_ids.contains(&type_id) {
+            return self;
+        }
+
+        self.tag_ids.push(type_id);
+        if let Some(component_type) = T::archetype_component() {
+            self.add_archetype_tag(component_type);
+        }
+
+        self
+    }
+
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        &self.archetype
+    }
+
+    /// Layout and the page with the single uninitialized entity of the archetype
+    fn new_row(archetype: &Archetype) -> (ArchetypeLayout, ArchetypeDataPage) {
+        let layout = ArchetypeLayout::single_row(archetype);
+        let mut row = ArchetypeDataPage::new(layout.page_size());
+        row.set_layout(&layout);
+        row.add_entity_id(0);
+
+        (layout, row)
+    }
+
+    /// Moves the prototype to the archetype with the zero-sized tag component
+    fn add_archetype_tag(&mut self, component_type: ComponentType) {
+        if self
+            .archetype
+            .find_component_index(component_type.id())
+            .is_some()
+        {
+            return;
+        }
+
+        let archetype = ArchetypeBuilder::new()
+            .include_archetype(&self.archetype)
+            .include_component_types(&[component_type])
+            .build();
+        let (layout, row) = Self::new_row(&archetype);
+
+        unsafe {
+            let dst_view = ArchetypeDataPageView {
+                archetype: &archetype,
+                layout: &layout,
+                page: &row,
+            };
+            ArchetypeDataPage::move_component_data_to_page_with_new_archetype(
+                &self.row_view(),
+                0,
+                &dst_view,
+                0,
+                0,
+            );
+        }
+
+        // Previous row is deallocated without the drop glue of the moved components
+        self.archetype = archetype;
+        self.layout = layout;
+        self.row = row;
+    }
+
+    fn row_view(&self) -> ArchetypeDataPageView<'_> {
+        ArchetypeDataPageView {
+            archetype: &self.archetype,
+            layout: &self.layout,
+            page: &self.row,
+        }
+    }
+}
+
+impl Drop for Prefab {
+    fn drop(&mut self) {
+        unsafe {
+            self.row
+                .drop_all_component_data(&self.archetype, &self.layout);
+        }
+    }
+}
+
+impl Store {
+    /// Creates `count` entities with the clones of the prefab components and tags.
+    /// Components are written straight to the archetype pages. Components with drop
+    /// glue must be registered with [`Store::register_clone`]. If the clone glue
+    /// panics, the instances, created before the panicked one, are kept
+    pub fn instantiate(&mut self, prefab: &Prefab, count: usize) -> Vec<Entity> {
+        let clone_fns = self.component_clone_fns.resolve(&prefab.archetype);
+        self.reserve_entities(count);
+
+        let tick = self.increment_change_tick();
+        let archetype_index = self
+            .archetypes_container
+            .reserve_archetype(&prefab.archetype);
+
+        let hooked_type_ids = if self.lifecycle_hooks.has_component_hooks() {
+            prefab.archetype.included_ids().to_vec()
+        } else {
+            Vec::new()
+        };
+
+        let mut entities = Vec::with_capacity(count);
+        for _ in 0..count {
+            let entity = self.create_entity_in_archetype_index(
+                archetype_index,
+                tick,
+                |container, dst| unsafe {
+                    let page_view =
+                        container.get_page_view_unchecked(dst.page_index as usize);
+                    prefab.row_view().clone_components_to(
+                        0,
+                        &page_view,
+                        dst.index_in_page as usize,
+                        &clone_fns,
+                    );
+                },
+            );
+
+            for &type_id in &prefab.tag_ids {
+                self.tag_container.add_tag_by_id(type_id, entity.id);
+            }
+
+            if !hooked_type_ids.is_empty() {
+                self.call_on_add_hooks(entity, &hooked_type_ids, None);
+            }
+
+            if self.lifecycle_hooks.has_tag_hooks() {
+                for &type_id in &prefab.tag_ids {
+                    self.lifecycle_hooks.call_on_tag_added(type_id, entity);
+                }
+            }
+
+            entities.push(entity);
+        }
+
+        entities
+    }
+}
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use crate::{
     archetype_data_page::ArchetypeDataPage, archetype_layout::ArchetypeLayout,
-    component_tuple::ComponentTuple, Archetype,
+    component_tuple::ComponentTuple, Archetype, ComponentId,
 };
 
 pub struct ReadonlyAccess<TRead: ComponentTuple> {
@@ -18,6 +18,31 @@ pub struct ReadWriteAccess<TRead: ComponentTuple, TWrite: ComponentTuple> {
     _write: PhantomData<TWrite>,
 }
 
+/// Matches only archetypes, that contain all `TFilter` components.
+/// The components themselves are not accessed
+pub struct With<TAccess: ComponentQueryAccess, TFilter: ComponentTuple> {
+    _access: PhantomData<TAccess>,
+    _filter: PhantomData<TFilter>,
+}
+
+/// Matches only archetypes, that contain none of the `TFilter` components
+pub struct Without<TAccess: ComponentQueryAccess, TFilter: ComponentTuple> {
+    _access: PhantomData<TAccess>,
+    _filter: PhantomData<TFilter>,
+}
+
+/// Additionally reads `TRead` components. It's `None`, when the archetype lacks any of them
+pub struct Optional<TAccess: ComponentQueryAccess, TRead: ComponentTuple> {
+    _access: PhantomData<TAccess>,
+    _read: PhantomData<TRead>,
+}
+
+/// Additionally writes `TWrite` components. It's `None`, when the archetype lacks any of them
+pub struct OptionalMut<TAccess: ComponentQueryAccess, TWrite: ComponentTuple> {
+    _access: PhantomData<TAccess>,
+    _write: PhantomData<TWrite>,
+}
+
 pub trait ComponentQueryAccess {
     type AccessOutput<'a>;
     type OffsetTuple: Default + Copy;
@@ -25,6 +50,12 @@ pub trait ComponentQueryAccess {
 
     fn is_archetype_include_types(archetype: &Archetype) -> bool;
 
+    /// Ids of the components, that are accessed for reading only
+    fn read_type_ids() -> Vec<ComponentId>;
+
+    /// Ids of the components, that are accessed for writing
+    fn write_type_ids() -> Vec<ComponentId>;
+
     fn get_refs<'a>(
         page: &'a ArchetypeDataPage,
         entity_index: usize,
@@ -71,6 +102,16 @@ where
             && TWrite::is_archetype_include_types(archetype)
     }
 
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        TRead::type_ids()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        TWrite::type_ids()
+    }
+
     #[inline(always)]
     fn get_refs<'a>(
         page: &'a ArchetypeDataPage,
@@ -126,6 +167,16 @@ impl<TRead: ComponentTuple> ComponentQueryAccess for ReadonlyAccess<TRead> {
         TRead::is_archetype_include_types(archetype)
     }
 
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        TRead::type_ids()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        Vec::new()
+    }
+
     #[inline(always)]
     fn get_refs<'a>(
         page: &'a ArchetypeDataPage,
@@ -171,6 +222,16 @@ impl<TWrite: ComponentTuple> ComponentQueryAccess for WriteAccess<TWrite> {
         TWrite::is_archetype_include_types(archetype)
     }
 
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        Vec::new()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        TWrite::type_ids()
+    }
+
     #[inline(always)]
     fn get_refs<'a>(
         page: &'a ArchetypeDataPage,
@@ -205,3 +266,287 @@ impl<TWrite: ComponentTuple> ComponentQueryAccess for WriteAccess<TWrite> {
         TWrite::ptrs_to_refs_mut(ptrs)
     }
 }
+
+impl<TAccess, TFilter> ComponentQueryAccess for With<TAccess, TFilter>
+where
+    TAccess: ComponentQueryAccess,
+    TFilter: ComponentTuple,
+{
+    type OffsetTuple = TAccess::OffsetTuple;
+    type AccessOutput<'a> = TAccess::AccessOutput<'a>;
+    type PtrTuple = TAccess::PtrTuple;
+
+    #[inline(always)]
+    fn is_archetype_include_types(archetype: &Archetype) -> bool {
+        TAccess::is_archetype_include_types(archetype)
+            && TFilter::is_archetype_include_types(archetype)
+    }
+
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        TAccess::read_type_ids()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        TAccess::write_type_ids()
+    }
+
+    #[inline(always)]
+    fn get_refs<'a>(
+        page: &'a ArchetypeDataPage,
+        entity_index: usize,
+        offsets: &Self::OffsetTuple,
+    ) -> Self::AccessOutput<'a> {
+        TAccess::get_refs(page, entity_index, offsets)
+    }
+
+    #[inline(always)]
+    fn get_offsets(archetype: &Archetype, layout: &ArchetypeLayout) -> Self::OffsetTuple {
+        TAccess::get_offsets(archetype, layout)
+    }
+
+    #[inline(always)]
+    fn get_ptrs(page: &ArchetypeDataPage, offsets: &Self::OffsetTuple) -> Self::PtrTuple {
+        TAccess::get_ptrs(page, offsets)
+    }
+
+    #[inline(always)]
+    fn add_to_ptrs(ptrs: &Self::PtrTuple, entity_index: usize) -> Self::PtrTuple {
+        TAccess::add_to_ptrs(ptrs, entity_index)
+    }
+
+    #[inline(always)]
+    fn null_ptrs() -> Self::PtrTuple {
+        TAccess::null_ptrs()
+    }
+
+    #[inline(always)]
+    fn ptrs_to_refs<'a>(ptrs: Self::PtrTuple) -> Self::AccessOutput<'a> {
+        TAccess::ptrs_to_refs(ptrs)
+    }
+}
+
+impl<TAccess, TFilter> ComponentQueryAccess for Without<TAccess, TFilter>
+where
+    TAccess: ComponentQueryAccess,
+    TFilter: ComponentTuple,
+{
+    type OffsetTuple = TAccess::OffsetTuple;
+    type AccessOutput<'a> = TAccess::AccessOutput<'a>;
+    type PtrTuple = TAccess::PtrTuple;
+
+    #[inline(always)]
+    fn is_archetype_include_types(archetype: &Archetype) -> bool {
+        TAccess::is_archetype_include_types(archetype)
+            && TFilter::is_archetype_exclude_types(archetype)
+    }
+
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        TAccess::read_type_ids()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        TAccess::write_type_ids()
+    }
+
+    #[inline(always)]
+    fn get_refs<'a>(
+        page: &'a ArchetypeDataPage,
+        entity_index: usize,
+        offsets: &Self::OffsetTuple,
+    ) -> Self::AccessOutput<'a> {
+        TAccess::get_refs(page, entity_index, offsets)
+    }
+
+    #[inline(always)]
+    fn get_offsets(archetype: &Archetype, layout: &ArchetypeLayout) -> Self::OffsetTuple {
+        TAccess::get_offsets(archetype, layout)
+    }
+
+    #[inline(always)]
+    fn get_ptrs(page: &ArchetypeDataPage, offsets: &Self::OffsetTuple) -> Self::PtrTuple {
+        TAccess::get_ptrs(page, offsets)
+    }
+
+    #[inline(always)]
+    fn add_to_ptrs(ptrs: &Self::PtrTuple, entity_index: usize) -> Self::PtrTuple {
+        TAccess::add_to_ptrs(ptrs, entity_index)
+    }
+
+    #[inline(always)]
+    fn null_ptrs() -> Self::PtrTuple {
+        TAccess::null_ptrs()
+    }
+
+    #[inline(always)]
+    fn ptrs_to_refs<'a>(ptrs: Self::PtrTuple) -> Self::AccessOutput<'a> {
+        TAccess::ptrs_to_refs(ptrs)
+    }
+}
+
+impl<TAccess, TRead> ComponentQueryAccess for Optional<TAccess, TRead>
+where
+    TAccess: ComponentQueryAccess,
+    TRead: ComponentTuple,
+{
+    type OffsetTuple = (TAccess::OffsetTuple, Option<TRead::OffsetTuple>);
+    type AccessOutput<'a> = (TAccess::AccessOutput<'a>, Option<TRead::RefTuple<'a>>);
+    type PtrTuple = (TAccess::PtrTuple, Option<TRead::PtrTuple>);
+
+    #[inline(always)]
+    fn is_archetype_include_types(archetype: &Archetype) -> bool {
+        TAccess::is_archetype_include_types(archetype)
+    }
+
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        let mut ids = TAccess::read_type_ids();
+        ids.extend(TRead::type_ids());
+        ids
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        TAccess::write_type_ids()
+    }
+
+    #[inline(always)]
+    fn get_refs<'a>(
+        page: &'a ArchetypeDataPage,
+        entity_index: usize,
+        (offsets, optional_offsets): &Self::OffsetTuple,
+    ) -> Self::AccessOutput<'a> {
+        (
+            TAccess::get_refs(page, entity_index, offsets),
+            optional_offsets.map(|o| TRead::get_refs(page, entity_index, &o)),
+        )
+    }
+
+    #[inline(always)]
+    fn get_offsets(archetype: &Archetype, layout: &ArchetypeLayout) -> Self::OffsetTuple {
+        (
+            TAccess::get_offsets(archetype, layout),
+            TRead::get_offsets(archetype, layout),
+        )
+    }
+
+    #[inline(always)]
+    fn get_ptrs(
+        page: &ArchetypeDataPage,
+        (offsets, optional_offsets): &Self::OffsetTuple,
+    ) -> Self::PtrTuple {
+        (
+            TAccess::get_ptrs(page, offsets),
+            optional_offsets.map(|o| TRead::get_ptrs(page, &o)),
+        )
+    }
+
+    #[inline(always)]
+    fn add_to_ptrs(
+        (ptrs, optional_ptrs): &Self::PtrTuple,
+        entity_index: usize,
+    ) -> Self::PtrTuple {
+        (
+            TAccess::add_to_ptrs(ptrs, entity_index),
+            optional_ptrs.map(|p| TRead::add_to_ptrs(&p, entity_index)),
+        )
+    }
+
+    #[inline(always)]
+    fn null_ptrs() -> Self::PtrTuple {
+        (TAccess::null_ptrs(), None)
+    }
+
+    #[inline(always)]
+    fn ptrs_to_refs<'a>((ptrs, optional_ptrs): Self::PtrTuple) -> Self::AccessOutput<'a> {
+        (
+            TAccess::ptrs_to_refs(ptrs),
+            optional_ptrs.map(|p| TRead::ptrs_to_refs(p)),
+        )
+    }
+}
+
+impl<TAccess, TWrite> ComponentQueryAccess for OptionalMut<TAccess, TWrite>
+where
+    TAccess: ComponentQueryAccess,
+    TWrite: ComponentTuple,
+{
+    type OffsetTuple = (TAccess::OffsetTuple, Option<TWrite::OffsetTuple>);
+    type AccessOutput<'a> = (TAccess::AccessOutput<'a>, Option<TWrite::MutRefTuple<'a>>);
+    type PtrTuple = (TAccess::PtrTuple, Option<TWrite::MutPtrTuple>);
+
+    #[inline(always)]
+    fn is_archetype_include_types(archetype: &Archetype) -> bool {
+        TAccess::is_archetype_include_types(archetype)
+    }
+
+    #[inline(always)]
+    fn read_type_ids() -> Vec<ComponentId> {
+        TAccess::read_type_ids()
+    }
+
+    #[inline(always)]
+    fn write_type_ids() -> Vec<ComponentId> {
+        let mut ids = TAccess::write_type_ids();
+        ids.extend(TWrite::type_ids());
+        ids
+    }
+
+    #[inline(always)]
+    fn get_refs<'a>(
+        page: &'a ArchetypeDataPage,
+        entity_index: usize,
+        (offsets, optional_offsets): &Self::OffsetTuple,
+    ) -> Self::AccessOutput<'a> {
+        (
+            TAccess::get_refs(page, entity_index, offsets),
+            optional_offsets.map(|o| TWrite::get_refs_mut(page, entity_index, &o)),
+        )
+    }
+
+    #[inline(always)]
+    fn get_offsets(archetype: &Archetype, layout: &ArchetypeLayout) -> Self::OffsetTuple {
+        (
+            TAccess::get_offsets(archetype, layout),
+            TWrite::get_offsets(archetype, layout),
+        )
+    }
+
+    #[inline(always)]
+    fn get_ptrs(
+        page: &ArchetypeDataPage,
+        (offsets, optional_offsets): &Self::OffsetTuple,
+    ) -> Self::PtrTuple {
+        (
+            TAccess::get_ptrs(page, offsets),
+            optional_offsets.map(|o| TWrite::get_ptrs_mut(page, &o)),
+        )
+    }
+
+    #[inline(always)]
+    fn add_to_ptrs(
+        (ptrs, optional_ptrs): &Self::PtrTuple,
+        entity_index: usize,
+    ) -> Self::PtrTuple {
+        (
+            TAccess::add_to_ptrs(ptrs, entity_index),
+            optional_ptrs.map(|p| TWrite::add_to_ptrs_mut(&p, entity_index)),
+        )
+    }
+
+    #[inline(always)]
+    fn null_ptrs() -> Self::PtrTuple {
+        (TAccess::null_ptrs(), None)
+    }
+
+    #[inline(always)]
+    fn ptrs_to_refs<'a>((ptrs, optional_ptrs): Self::PtrTuple) -> Self::AccessOutput<'a> {
+        (
+            TAccess::ptrs_to_refs(ptrs),
+            optional_ptrs.map(|p| TWrite::ptrs_to_refs_mut(p)),
+        )
+    }
+}
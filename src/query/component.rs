@@ -1,8 +1,14 @@
 use crate::{
-    bitvec_utils,
+    archetype_data_page::ArchetypeDataPage,
+    archetypes_container::ArchetypesContainer,
     component_tuple::ComponentTuple,
-    query::access::{ComponentQueryAccess, ReadWriteAccess, ReadonlyAccess, WriteAccess},
-    Entity, Store, Tag,
+    query::access::{
+        ComponentQueryAccess, Optional, OptionalMut, ReadWriteAccess, ReadonlyAccess,
+        With, Without, WriteAccess,
+    },
+    tag_container::{TagContainer, TagFilterMask},
+    tag_tuple::TagTuple,
+    tick_utils, Archetype, Component, ComponentId, Entity, Store, Tag,
 };
 
 use super::{
@@ -16,6 +22,16 @@ pub type ComponentWriteQuery<W> = ComponentQuery<WriteAccess<W>>;
 
 pub struct ComponentQuery<T: ComponentQueryAccess> {
     page_views: Vec<ComponentPageIterView<T>>,
+
+    matched_archetypes: Vec<usize>,
+    archetypes_container_id: Option<u64>,
+    archetypes_generation: u64,
+
+    changed_filter: Vec<ComponentId>,
+    added_filter: Vec<ComponentId>,
+    /// Archetypes container id and change tick of the last run. Ticks of the other
+    /// store aren't comparable, so all components are new for it
+    last_run: Option<(u64, u32)>,
 }
 
 pub struct ComponentsQueryIter<'a, T: ComponentQueryAccess> {
@@ -32,9 +48,35 @@ pub struct WithEntitiesIter<'a, T: ComponentQueryAccess> {
     entities_versions: *const u32,
 }
 
+/// Entities iterator, filtered by the tags. Single tag filter checks the tag bit
+/// vector directly. Multiple tag filters are combined into the single entities mask
+/// word by word, so every entity is checked once
 pub struct FilterTagIter<'a, T: ComponentQueryAccess> {
     source_iter: WithEntitiesIter<'a, T>,
-    entity_to_has_tag: *const u32,
+    entities_mask: TagFilterMask<'a>,
+}
+
+/// Contiguous page views chunk, that is iterated by a single worker thread
+struct PageViewsChunk<'a, T: ComponentQueryAccess> {
+    page_views: &'a [ComponentPageIterView<T>],
+}
+
+// Each page view is iterated by exactly one thread, so the mutable accesses never alias
+unsafe impl<'a, T: ComponentQueryAccess> Send for PageViewsChunk<'a, T> where
+    T::AccessOutput<'a>: Send
+{
+}
+
+/// Entity versions are only read while the store is borrowed by the query
+struct EntityVersions(*const u32);
+
+unsafe impl Sync for EntityVersions {}
+
+#[inline]
+fn default_threads_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
 }
 
 pub fn readonly<R: ComponentTuple>() -> ComponentQuery<ReadonlyAccess<R>> {
@@ -54,7 +96,204 @@ impl<T: ComponentQueryAccess> ComponentQuery<T> {
     pub fn new() -> Self {
         ComponentQuery {
             page_views: Vec::new(),
+
+            matched_archetypes: Vec::new(),
+            archetypes_container_id: None,
+            archetypes_generation: 0,
+
+            changed_filter: Vec::new(),
+            added_filter: Vec::new(),
+            last_run: None,
+        }
+    }
+
+    /// Keeps only the pages, where the component was changed since the last query run.
+    /// The granularity is the page, so unchanged entities of the changed page are also queried
+    pub fn changed<C: Component + 'static>(mut self) -> Self {
+        self.changed_filter.push(ComponentId::of::<C>());
+        self.reset_matched_archetypes();
+        self
+    }
+
+    /// Keeps only the entities, that got the component since the last query run
+    pub fn added<C: Component + 'static>(mut self) -> Self {
+        self.added_filter.push(ComponentId::of::<C>());
+        self.reset_matched_archetypes();
+        self
+    }
+
+    /// Keeps only the entities, that have all `F` components
+    pub fn with<F: ComponentTuple>(self) -> ComponentQuery<With<T, F>> {
+        self.into_access()
+    }
+
+    /// Keeps only the entities, that have none of `F` components
+    pub fn without<F: ComponentTuple>(self) -> ComponentQuery<Without<T, F>> {
+        self.into_access()
+    }
+
+    /// Additionally reads `R` components, if the entity has them
+    pub fn optional<R: ComponentTuple>(self) -> ComponentQuery<Optional<T, R>> {
+        self.into_access()
+    }
+
+    /// Additionally writes `W` components, if the entity has them
+    pub fn optional_mut<W: ComponentTuple>(self) -> ComponentQuery<OptionalMut<T, W>> {
+        self.into_access()
+    }
+
+    fn into_access<A: ComponentQueryAccess>(self) -> ComponentQuery<A> {
+        ComponentQuery {
+            page_views: Vec::new(),
+
+            matched_archetypes: Vec::new(),
+            archetypes_container_id: None,
+            archetypes_generation: 0,
+
+            changed_filter: self.changed_filter,
+            added_filter: self.added_filter,
+            last_run: self.last_run,
+        }
+    }
+
+    /// Filters change the matching predicate, so the archetypes are matched again
+    fn reset_matched_archetypes(&mut self) {
+        self.matched_archetypes.clear();
+        self.archetypes_container_id = None;
+        self.archetypes_generation = 0;
+    }
+
+    /// Checks only archetypes, that were created since the previous call.
+    /// The whole cache is rebuilt, when the query is used with the other store
+    fn update_matched_archetypes(&mut self, arch_container: &ArchetypesContainer) {
+        let generation = arch_container.generation();
+        if self.archetypes_container_id != Some(arch_container.id()) {
+            self.archetypes_container_id = Some(arch_container.id());
+            self.archetypes_generation = 0;
+            self.matched_archetypes.clear();
+        } else if self.archetypes_generation == generation {
+            return;
+        }
+
+        for arch_idx in 0..arch_container.get_archetypes().len() {
+            if arch_container.get_archetype_generation(arch_idx)
+                <= self.archetypes_generation
+            {
+                continue;
+            }
+
+            let position = self.matched_archetypes.binary_search(&arch_idx);
+            let arch = &arch_container.get_archetypes()[arch_idx];
+            let is_matched = T::is_archetype_include_types(arch)
+                && arch.is_include_ids(&self.changed_filter)
+                && arch.is_include_ids(&self.added_filter);
+
+            match (position, is_matched) {
+                (Ok(i), false) => {
+                    self.matched_archetypes.remove(i);
+                }
+                (Err(i), true) => self.matched_archetypes.insert(i, arch_idx),
+                _ => {}
+            }
+        }
+
+        self.archetypes_generation = generation;
+    }
+
+    #[inline]
+    fn has_change_filters(&self) -> bool {
+        !self.changed_filter.is_empty() || !self.added_filter.is_empty()
+    }
+
+    /// Tick of the last run with the store. `None`, if the query wasn't run with it
+    fn last_run_tick(&self, container_id: u64) -> Option<u32> {
+        self.last_run
+            .and_then(|(id, tick)| (id == container_id).then_some(tick))
+    }
+
+    #[inline]
+    fn is_tick_after_last_run(
+        tick: u32,
+        last_run_tick: Option<u32>,
+        this_run_tick: u32,
+    ) -> bool {
+        last_run_tick.is_none_or(|last_run_tick| {
+            tick_utils::is_newer(tick, last_run_tick, this_run_tick)
+        })
+    }
+
+    fn is_page_changed(
+        &self,
+        page: &ArchetypeDataPage,
+        archetype: &Archetype,
+        last_run_tick: Option<u32>,
+        this_run_tick: u32,
+    ) -> bool {
+        self.changed_filter.iter().all(|id| {
+            archetype.find_component_index(*id).is_some_and(|idx| {
+                let tick = page.component_changed_tick(idx);
+                Self::is_tick_after_last_run(tick, last_run_tick, this_run_tick)
+            })
+        })
+    }
+
+    fn is_entity_added(
+        &self,
+        page: &ArchetypeDataPage,
+        archetype: &Archetype,
+        entity_index: usize,
+        last_run_tick: Option<u32>,
+        this_run_tick: u32,
+    ) -> bool {
+        self.added_filter.iter().all(|id| {
+            archetype.find_component_index(*id).is_some_and(|idx| {
+                let tick = page.component_added_tick(idx, entity_index);
+                Self::is_tick_after_last_run(tick, last_run_tick, this_run_tick)
+            })
+        })
+    }
+
+    /// Pushes views of the contiguous entity ranges, that pass the added filter.
+    /// Returns count of the queried entities
+    fn push_added_page_views(
+        &mut self,
+        page: &ArchetypeDataPage,
+        archetype: &Archetype,
+        page_view: ComponentPageIterView<T>,
+        last_run_tick: Option<u32>,
+        this_run_tick: u32,
+    ) -> usize {
+        let mut queried_entities_count = 0;
+        let mut range_start = None;
+
+        for entity_index in 0..=page_view.entity_count {
+            let is_added = entity_index < page_view.entity_count
+                && self.is_entity_added(
+                    page,
+                    archetype,
+                    entity_index,
+                    last_run_tick,
+                    this_run_tick,
+                );
+
+            match (range_start, is_added) {
+                (None, true) => range_start = Some(entity_index),
+                (Some(start), false) => {
+                    let count = entity_index - start;
+                    self.page_views.push(ComponentPageIterView {
+                        ptrs: T::add_to_ptrs(&page_view.ptrs, start),
+                        entity_ids: unsafe { page_view.entity_ids.add(start) },
+                        entity_count: count,
+                    });
+
+                    queried_entities_count += count;
+                    range_start = None;
+                }
+                _ => {}
+            }
         }
+
+        queried_entities_count
     }
 }
 
@@ -67,18 +306,21 @@ impl Store {
         let pages = arch_container.get_pages();
         let mut queried_entities_count = 0;
 
+        let this_run_tick = self.increment_change_tick();
+        let write_type_ids = T::write_type_ids();
+        let has_change_filters = query.has_change_filters();
+        let last_run_tick = query.last_run_tick(arch_container.id());
+
         query.page_views.clear();
+        query.update_matched_archetypes(arch_container);
 
-        for arch_idx in 0..arch_container.get_archetypes().len() {
+        for matched_idx in 0..query.matched_archetypes.len() {
+            let arch_idx = query.matched_archetypes[matched_idx];
             let (arch, layout) = unsafe {
                 self.archetypes_container
                     .get_archetype_with_layout_unchecked(arch_idx)
             };
 
-            if T::is_archetype_include_types(arch) == false {
-                continue;
-            }
-
             let component_offsets = T::get_offsets(arch, layout);
 
             let arch_pages = arch_container.get_archetype_page_indices(arch_idx);
@@ -90,15 +332,41 @@ impl Store {
                     continue;
                 }
 
-                query.page_views.push(ComponentPageIterView {
+                if has_change_filters
+                    && !query.is_page_changed(page, arch, last_run_tick, this_run_tick)
+                {
+                    continue;
+                }
+
+                let page_view = ComponentPageIterView {
                     ptrs: T::get_ptrs(page, &component_offsets),
                     entity_count: page.entity_count(),
                     entity_ids: unsafe { page.entity_id_ptrs() },
-                });
-                queried_entities_count += page_entities_count;
+                };
+
+                let page_queried_count = if !query.added_filter.is_empty() {
+                    query.push_added_page_views(
+                        page,
+                        arch,
+                        page_view,
+                        last_run_tick,
+                        this_run_tick,
+                    )
+                } else {
+                    query.page_views.push(page_view);
+                    page_entities_count
+                };
+
+                if page_queried_count > 0 {
+                    page.mark_components_changed(arch, &write_type_ids, this_run_tick);
+                }
+
+                queried_entities_count += page_queried_count;
             }
         }
 
+        query.last_run = Some((arch_container.id(), this_run_tick));
+
         let page_iter = if query.page_views.len() > 0 {
             let page_view = query.page_views[0];
 
@@ -157,6 +425,84 @@ impl<'a, T: ComponentQueryAccess> ComponentsQueryIter<'a, T> {
             entities_versions,
         }
     }
+
+    /// Calls `f` for the rest of the queried components, spreading data pages
+    /// across the scoped worker threads. The order of calls is unspecified
+    pub fn par_for_each<F>(self, f: F)
+    where
+        F: Fn(T::AccessOutput<'a>) + Sync,
+        T::AccessOutput<'a>: Send,
+    {
+        self.par_for_each_with_threads(default_threads_count(), f);
+    }
+
+    /// Same as [`Self::par_for_each`], but with the explicit upper bound of threads
+    pub fn par_for_each_with_threads<F>(self, threads_count: usize, f: F)
+    where
+        F: Fn(T::AccessOutput<'a>) + Sync,
+        T::AccessOutput<'a>: Send,
+    {
+        self.par_for_each_page(threads_count, |page_iter| page_iter.for_each(&f));
+    }
+
+    fn par_for_each_page<F>(self, threads_count: usize, f: F)
+    where
+        F: Fn(ComponentPageIter<'a, T>) + Sync,
+        T::AccessOutput<'a>: Send,
+    {
+        let rest_page_views = self
+            .page_views
+            .get(self.current_page_index + 1..)
+            .unwrap_or_default();
+
+        let threads_count = threads_count.min(rest_page_views.len());
+
+        if threads_count <= 1 {
+            f(self.current_page_iter);
+            rest_page_views
+                .iter()
+                .for_each(|view| f(ComponentPageIter::new(*view)));
+
+            return;
+        }
+
+        let rest_entities_count: usize =
+            rest_page_views.iter().map(|v| v.entity_count).sum();
+        let entities_per_thread = rest_entities_count.div_ceil(threads_count);
+
+        std::thread::scope(|scope| {
+            let f = &f;
+            let mut chunk_start = 0;
+            let mut chunk_entities_count = 0;
+
+            for (i, view) in rest_page_views.iter().enumerate() {
+                chunk_entities_count += view.entity_count;
+
+                let is_last = i == rest_page_views.len() - 1;
+                if chunk_entities_count < entities_per_thread && !is_last {
+                    continue;
+                }
+
+                let chunk = PageViewsChunk {
+                    page_views: &rest_page_views[chunk_start..=i],
+                };
+                scope.spawn(move || chunk.for_each_page(f));
+
+                chunk_start = i + 1;
+                chunk_entities_count = 0;
+            }
+
+            f(self.current_page_iter);
+        });
+    }
+}
+
+impl<'a, T: ComponentQueryAccess> PageViewsChunk<'a, T> {
+    fn for_each_page<F: Fn(ComponentPageIter<'a, T>)>(self, f: &F) {
+        for view in self.page_views {
+            f(ComponentPageIter::new(*view));
+        }
+    }
 }
 
 impl<'a, T: ComponentQueryAccess> Iterator for WithEntitiesIter<'a, T> {
@@ -173,27 +519,139 @@ impl<'a, T: ComponentQueryAccess> Iterator for WithEntitiesIter<'a, T> {
 }
 
 impl<'a, Q: ComponentQueryAccess> WithEntitiesIter<'a, Q> {
-    pub fn filter_tag<T: Tag + 'static>(self) -> FilterTagIter<'a, Q> {
-        let tag_container = &self.source_iter.store.tag_container;
-        let entity_to_has_tag = tag_container.has_tag_bitvec::<T>();
+    /// Parallel version of the iteration with entities.
+    /// See [`ComponentsQueryIter::par_for_each`]
+    pub fn par_for_each<F>(self, f: F)
+    where
+        F: Fn(Entity, Q::AccessOutput<'a>) + Sync,
+        Q::AccessOutput<'a>: Send,
+    {
+        self.par_for_each_with_threads(default_threads_count(), f);
+    }
+
+    pub fn par_for_each_with_threads<F>(self, threads_count: usize, f: F)
+    where
+        F: Fn(Entity, Q::AccessOutput<'a>) + Sync,
+        Q::AccessOutput<'a>: Send,
+    {
+        let entities_versions = EntityVersions(self.entities_versions);
+        let entities_versions = &entities_versions;
+
+        self.source_iter
+            .par_for_each_page(threads_count, |mut page_iter| {
+                while let Some(output) = page_iter.next() {
+                    let entity = unsafe {
+                        let id = page_iter.current_entity_id();
+                        Entity {
+                            id,
+                            version: *entities_versions.0.add(id as usize),
+                        }
+                    };
+
+                    f(entity, output);
+                }
+            });
+    }
+
+    /// Keeps entities with the tag. No entities pass, if the tag was never added
+    pub fn filter_tag<T: Tag>(self) -> FilterTagIter<'a, Q> {
+        self.into_filter_tag_iter().filter_tag::<T>()
+    }
+
+    /// Keeps entities with all the tags
+    pub fn filter_tags<T: TagTuple>(self) -> FilterTagIter<'a, Q> {
+        self.into_filter_tag_iter().filter_tags::<T>()
+    }
+
+    /// Keeps entities with at least one of the tags
+    pub fn filter_any_tag<T: TagTuple>(self) -> FilterTagIter<'a, Q> {
+        self.into_filter_tag_iter().filter_any_tag::<T>()
+    }
+
+    /// Skips entities with the tag
+    pub fn exclude_tag<T: Tag>(self) -> FilterTagIter<'a, Q> {
+        self.into_filter_tag_iter().exclude_tag::<T>()
+    }
 
+    /// Skips entities with any of the tags
+    pub fn exclude_tags<T: TagTuple>(self) -> FilterTagIter<'a, Q> {
+        self.into_filter_tag_iter().exclude_tags::<T>()
+    }
+
+    fn into_filter_tag_iter(self) -> FilterTagIter<'a, Q> {
         FilterTagIter {
             source_iter: self,
-            entity_to_has_tag,
+            entities_mask: TagFilterMask::All,
         }
     }
 }
 
+impl<'a, Q: ComponentQueryAccess> FilterTagIter<'a, Q> {
+    pub fn filter_tag<T: Tag>(self) -> Self {
+        self.filter_tags::<T>()
+    }
+
+    pub fn filter_tags<T: TagTuple>(mut self) -> Self {
+        let tag_container = self.tag_container();
+        let type_ids = T::type_ids();
+        self.entities_mask = match (self.take_entities_mask(), type_ids.as_slice()) {
+            (TagFilterMask::All, &[type_id]) => {
+                TagFilterMask::WithTag(tag_container.get_has_tag_words(type_id))
+            }
+            (entities_mask, _) => {
+                let mut mask = entities_mask.into_combined(tag_container);
+                tag_container.intersect_mask_with_all(&mut mask, &type_ids);
+                TagFilterMask::Combined(mask)
+            }
+        };
+        self
+    }
+
+    pub fn filter_any_tag<T: TagTuple>(mut self) -> Self {
+        let tag_container = self.tag_container();
+        let mut mask = self.take_entities_mask().into_combined(tag_container);
+        tag_container.intersect_mask_with_any(&mut mask, &T::type_ids());
+        self.entities_mask = TagFilterMask::Combined(mask);
+        self
+    }
+
+    pub fn exclude_tag<T: Tag>(self) -> Self {
+        self.exclude_tags::<T>()
+    }
+
+    pub fn exclude_tags<T: TagTuple>(mut self) -> Self {
+        let tag_container = self.tag_container();
+        let type_ids = T::type_ids();
+        self.entities_mask = match (self.take_entities_mask(), type_ids.as_slice()) {
+            (TagFilterMask::All, &[type_id]) => {
+                TagFilterMask::WithoutTag(tag_container.get_has_tag_words(type_id))
+            }
+            (entities_mask, _) => {
+                let mut mask = entities_mask.into_combined(tag_container);
+                tag_container.subtract_mask_tags(&mut mask, &type_ids);
+                TagFilterMask::Combined(mask)
+            }
+        };
+        self
+    }
+
+    #[inline]
+    fn tag_container(&self) -> &'a TagContainer {
+        let store: &'a Store = self.source_iter.source_iter.store;
+        &store.tag_container
+    }
+
+    #[inline]
+    fn take_entities_mask(&mut self) -> TagFilterMask<'a> {
+        std::mem::replace(&mut self.entities_mask, TagFilterMask::All)
+    }
+}
+
 impl<'a, Q: ComponentQueryAccess> Iterator for FilterTagIter<'a, Q> {
     type Item = (Entity, Q::AccessOutput<'a>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.source_iter.next().and_then(|(e, o)| unsafe {
-            if bitvec_utils::is_bit_on(self.entity_to_has_tag, e.id as usize) {
-                Some((e, o))
-            } else {
-                self.next()
-            }
-        })
+        let entities_mask = &self.entities_mask;
+        self.source_iter.find(|(e, _)| entities_mask.contains(e.id))
     }
 }
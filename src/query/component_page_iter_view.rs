@@ -9,6 +9,13 @@ pub struct ComponentPageIterView<T: ComponentQueryAccess> {
     pub entity_count: usize,
 }
 
+// Pointers of the view are dereferenced only as the `AccessOutput` references,
+// so the view can be sent, when they can
+unsafe impl<T: ComponentQueryAccess> Send for ComponentPageIterView<T> where
+    for<'a> T::AccessOutput<'a>: Send
+{
+}
+
 impl<T: ComponentQueryAccess> ComponentPageIterView<T> {
     pub unsafe fn new(page: &ArchetypeDataPage, component_offsets: &T::OffsetTuple) -> Self {
         Self {
@@ -64,6 +64,9 @@ impl Store {
         query.entity_index_ranges.clear();
         query.range_to_page_views.clear();
 
+        let tick = self.increment_change_tick();
+        let write_type_ids = T::write_type_ids();
+
         let mut range_start = 0;
         while range_start < entities.len() {
             let range_entity = entities[range_start];
@@ -87,6 +90,7 @@ impl Store {
                     .get_unchecked(range_page_index)
             };
             let comp_offsets = T::get_offsets(&arch, &layout);
+            page.mark_components_changed(arch, &write_type_ids, tick);
 
             let mut range_end = range_start + 1;
             while range_end < entities.len() {
@@ -86,6 +86,8 @@ impl Store {
         };
 
         let mut entity_count = 0;
+        let tick = self.increment_change_tick();
+        let write_type_ids = T::write_type_ids();
 
         while curr_entity != Entity::NULL_ID {
             let (curr_page_index, curr_index_in_page) = unsafe {
@@ -112,6 +114,7 @@ impl Store {
                 }
 
                 let offsets = T::get_offsets(arch, layout);
+                page.mark_components_changed(arch, &write_type_ids, tick);
 
                 (page, offsets)
             };
@@ -0,0 +1,253 @@
+use std::any::TypeId;
+
+use crate::{Component, ComponentId, Entity, Store};
+
+/// Fields metadata of the component. It's implemented by `#[derive(Component)]`
+/// with the `#[component(reflect)]` attribute
+///
+/// # Safety
+/// Every field info must describe the real field of the component: the offset and
+/// the size are inside the component and the type is the type of the field, since
+/// the store reads and writes the fields by them
+pub unsafe trait Reflect: Component + 'static {
+    fn reflect_fields() -> Vec<FieldInfo>;
+
+    /// Metadata of the field with the offset. `get` only infers the field type
+    fn reflect_field<F: 'static>(
+        name: &'static str,
+        offset: usize,
+        _get: fn(&Self) -> &F,
+    ) -> FieldInfo {
+        FieldInfo {
+            name,
+            offset,
+            size: std::mem::size_of::<F>(),
+            type_id: TypeId::of::<F>(),
+            type_name: std::any::type_name::<F>(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    name: &'static str,
+    offset: usize,
+    size: usize,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+/// Component type with its fields metadata, registered by [`Store::register_reflect`]
+#[derive(Debug, Clone)]
+pub struct ComponentReflection {
+    type_id: TypeId,
+    name: &'static str,
+    size: usize,
+    fields: Vec<FieldInfo>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct ReflectionRegistry {
+    type_ids: Vec<TypeId>,
+    reflections: Vec<ComponentReflection>,
+}
+
+impl FieldInfo {
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Offset of the field from the start of the component in bytes
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline(always)]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl ComponentReflection {
+    #[inline(always)]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline(always)]
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl ReflectionRegistry {
+    pub fn register<T: Reflect>(&mut self) {
+        let type_id = TypeId::of::<T>();
+        if let Err(i) = self.type_ids.binary_search(&type_id) {
+            self.type_ids.insert(i, type_id);
+            self.reflections.insert(
+                i,
+                ComponentReflection {
+                    type_id,
+                    name: std::any::type_name::<T>(),
+                    size: std::mem::size_of::<T>(),
+                    fields: T::reflect_fields(),
+                },
+            );
+        }
+    }
+
+    pub fn get(&self, type_id: TypeId) -> Option<&ComponentReflection> {
+        self.type_ids
+            .binary_search(&type_id)
+            .ok()
+            .map(|i| &self.reflections[i])
+    }
+}
+
+impl Store {
+    /// Registers the fields metadata of the component for the reflection methods
+    pub fn register_reflect<T: Reflect>(&mut self) {
+        self.reflection_registry.register::<T>();
+    }
+
+    pub fn get_component_reflection(
+        &self,
+        type_id: TypeId,
+    ) -> Option<&ComponentReflection> {
+        self.reflection_registry.get(type_id)
+    }
+
+    /// Reflections of the registered entity components in the archetype order.
+    /// Components without registered reflection are skipped
+    pub fn reflect_components(&self, entity: Entity) -> Vec<&ComponentReflection> {
+        match self.get_entity_archetype(entity) {
+            Some(archetype) => archetype
+                .included_ids()
+                .iter()
+                .filter_map(|&id| match id {
+                    ComponentId::Type(type_id) => self.reflection_registry.get(type_id),
+                    ComponentId::Dynamic(_) => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Bytes of the component field. `None`, if the entity doesn't have the component
+    /// with registered reflection or the component doesn't have the field
+    ///
+    /// # Safety
+    /// The field type must not contain padding or other uninitialized bytes,
+    /// e.g. it's the integer, the float or the array of them
+    pub unsafe fn get_field_bytes(
+        &self,
+        entity: Entity,
+        component_id: TypeId,
+        field_name: &str,
+    ) -> Option<&[u8]> {
+        let (ptr, field) = self.get_field_ptr(entity, component_id, field_name)?;
+        if field.size == 0 {
+            return Some(&[]);
+        }
+
+        Some(std::slice::from_raw_parts(ptr, field.size))
+    }
+
+    /// Reference to the component field. Panics, if the field isn't `F`
+    pub fn get_field<F: 'static>(
+        &self,
+        entity: Entity,
+        component_id: TypeId,
+        field_name: &str,
+    ) -> Option<&F> {
+        let (ptr, field) = self.get_field_ptr(entity, component_id, field_name)?;
+        Self::assert_field_type::<F>(&field);
+
+        Some(unsafe { &*(ptr as *const F) })
+    }
+
+    /// Mutable reference to the component field, the component is marked as changed.
+    /// Panics, if the field isn't `F`
+    pub fn get_field_mut<F: 'static>(
+        &mut self,
+        entity: Entity,
+        component_id: TypeId,
+        field_name: &str,
+    ) -> Option<&mut F> {
+        let (ptr, field) = self.get_field_ptr(entity, component_id, field_name)?;
+        Self::assert_field_type::<F>(&field);
+
+        let tick = self.increment_change_tick();
+        unsafe {
+            let (page_view, _) = self.get_page_info(entity.id);
+            page_view.page.mark_components_changed(
+                page_view.archetype,
+                &[ComponentId::from(component_id)],
+                tick,
+            );
+
+            Some(&mut *(ptr as *mut F))
+        }
+    }
+
+    fn get_field_ptr(
+        &self,
+        entity: Entity,
+        component_id: TypeId,
+        field_name: &str,
+    ) -> Option<(*const u8, FieldInfo)> {
+        if !self.is_valid_entity_with_archetype(entity) {
+            return None;
+        }
+
+        let field = *self
+            .reflection_registry
+            .get(component_id)?
+            .field(field_name)?;
+        let (page_view, index_in_page) = unsafe { self.get_page_info(entity.id) };
+        let component_index = page_view
+            .archetype
+            .find_component_index(ComponentId::from(component_id))?;
+        let component_ptr =
+            page_view.get_component_data_ptr(component_index, index_in_page);
+
+        Some((unsafe { component_ptr.add(field.offset) }, field))
+    }
+
+    fn assert_field_type<F: 'static>(field: &FieldInfo) {
+        assert!(
+            field.type_id == TypeId::of::<F>(),
+            "Field {} is {}, not {}",
+            field.name,
+            field.type_name,
+            std::any::type_name::<F>()
+        );
+    }
+}
@@ -5,6 +5,7 @@ use crate::{entities_container::EntitiesContainer, mem_utils, Entity};
 pub struct ResourceStore<T> {
     elements: *mut ManuallyDrop<T>,
     entities_container: EntitiesContainer,
+    len: usize,
 }
 
 impl<T> ResourceStore<T> {
@@ -12,6 +13,7 @@ impl<T> ResourceStore<T> {
         ResourceStore {
             elements: std::ptr::null_mut(),
             entities_container: EntitiesContainer::new(0),
+            len: 0,
         }
     }
 
@@ -19,6 +21,7 @@ impl<T> ResourceStore<T> {
         ResourceStore {
             elements: unsafe { mem_utils::alloc(capacity) },
             entities_container: EntitiesContainer::new(capacity),
+            len: 0,
         }
     }
 
@@ -40,42 +43,210 @@ impl<T> ResourceStore<T> {
         unsafe {
             self.elements.add(entity.id as usize).write(ManuallyDrop::new(resource));
         }
+        self.len += 1;
 
         entity
     }
 
+    /// Removes the resource and returns it. Panics, if the entity is not alive
     pub fn release(&mut self, entity: Entity) -> T {
-        debug_assert!(self.entities_container.is_alive(entity));
+        assert!(self.contains(entity), "Resource entity is not alive");
+        unsafe { self.release_unchecked(entity) }
+    }
+
+    /// Removes the resource, if the entity is alive
+    pub fn try_release(&mut self, entity: Entity) -> Option<T> {
+        if self.contains(entity) {
+            Some(unsafe { self.release_unchecked(entity) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities_container.validate_id(entity.id)
+            && self.entities_container.is_alive(entity)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        if self.contains(entity) {
+            Some(unsafe { self.get_unchecked(entity.id as usize) })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        if self.contains(entity) {
+            Some(unsafe { self.get_unchecked_mut(entity.id as usize) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> ResourceStoreIter<'_, T> {
+        ResourceStoreIter {
+            store: self,
+            next_index: 0,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> ResourceStoreIterMut<'_, T> {
+        ResourceStoreIterMut {
+            store: self,
+            next_index: 0,
+        }
+    }
+
+    /// Keeps only the resources, for which `f` returns `true`. Other resources are dropped
+    pub fn retain(&mut self, mut f: impl FnMut(Entity, &mut T) -> bool) {
+        for index in 0..self.entities_container.capacity() {
+            if !self.entities_container.is_alive_at_index(index) {
+                continue;
+            }
+
+            let entity = unsafe { self.get_entity_unchecked(index) };
+            if !f(entity, unsafe { self.get_unchecked_mut(index) }) {
+                drop(unsafe { self.release_unchecked(entity) });
+            }
+        }
+    }
+
+    /// Removes all resources and returns them with their entities. Resources, which
+    /// are not taken from the iterator, are dropped
+    pub fn drain(&mut self) -> ResourceStoreDrain<'_, T> {
+        ResourceStoreDrain {
+            store: self,
+            next_index: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+
+    unsafe fn release_unchecked(&mut self, entity: Entity) -> T {
         self.entities_container.destroy_entity(entity);
-        unsafe { 
-            ManuallyDrop::take(&mut self.elements.add(entity.id as usize).read())
+        self.len -= 1;
+        ManuallyDrop::take(&mut self.elements.add(entity.id as usize).read())
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(&self, index: usize) -> &T {
+        &*self.elements.add(index)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        &mut *self.elements.add(index)
+    }
+
+    #[inline]
+    unsafe fn get_entity_unchecked(&self, index: usize) -> Entity {
+        Entity {
+            id: index as u32,
+            version: *self.entities_container.entity_versions().add(index),
+        }
+    }
+
+    /// Index of the next alive resource starting from `index`
+    fn find_alive_index(&self, index: usize) -> Option<usize> {
+        (index..self.entities_container.capacity())
+            .find(|&i| self.entities_container.is_alive_at_index(i))
+    }
+}
+
+/// Iterator over the alive resources with their entities
+pub struct ResourceStoreIter<'a, T> {
+    store: &'a ResourceStore<T>,
+    next_index: usize,
+}
+
+pub struct ResourceStoreIterMut<'a, T> {
+    store: &'a mut ResourceStore<T>,
+    next_index: usize,
+}
+
+pub struct ResourceStoreDrain<'a, T> {
+    store: &'a mut ResourceStore<T>,
+    next_index: usize,
+}
+
+impl<'a, T> Iterator for ResourceStoreIter<'a, T> {
+    type Item = (Entity, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.store.find_alive_index(self.next_index)?;
+        self.next_index = index + 1;
+
+        unsafe {
+            Some((
+                self.store.get_entity_unchecked(index),
+                self.store.get_unchecked(index),
+            ))
         }
     }
 }
 
+impl<'a, T> Iterator for ResourceStoreIterMut<'a, T> {
+    type Item = (Entity, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.store.find_alive_index(self.next_index)?;
+        self.next_index = index + 1;
+
+        // Every index is visited once, so the mutable references never alias
+        unsafe {
+            Some((
+                self.store.get_entity_unchecked(index),
+                &mut *self.store.elements.add(index).cast::<T>(),
+            ))
+        }
+    }
+}
+
+impl<'a, T> Iterator for ResourceStoreDrain<'a, T> {
+    type Item = (Entity, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.store.find_alive_index(self.next_index)?;
+        self.next_index = index + 1;
+
+        unsafe {
+            let entity = self.store.get_entity_unchecked(index);
+            Some((entity, self.store.release_unchecked(entity)))
+        }
+    }
+}
+
+impl<'a, T> Drop for ResourceStoreDrain<'a, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
 impl<T> Index<Entity> for ResourceStore<T> {
     type Output = T;
 
     fn index(&self, entity: Entity) -> &Self::Output {
-        debug_assert!(self.entities_container.is_alive(entity));
-        unsafe {
-            self.elements
-                .add(entity.id as usize)
-                .as_ref()
-                .unwrap_unchecked()
-        }
+        self.get(entity).expect("Resource entity is not alive")
     }
 }
 
 impl<T> IndexMut<Entity> for ResourceStore<T> {
     fn index_mut(&mut self, entity: Entity) -> &mut Self::Output {
-        debug_assert!(self.entities_container.is_alive(entity));
-        unsafe {
-            self.elements
-                .add(entity.id as usize)
-                .as_mut()
-                .unwrap_unchecked()
-        }
+        self.get_mut(entity).expect("Resource entity is not alive")
     }
 }
 
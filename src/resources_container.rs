@@ -0,0 +1,176 @@
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError},
+};
+
+use crate::Store;
+
+type BoxedResource = Box<dyn Any + Send + Sync>;
+
+/// Singleton resources of the store, keyed by the type. Every resource is borrowed
+/// separately, so it can be accessed with the shared store reference while
+/// the queries are running
+pub(crate) struct ResourcesContainer {
+    type_ids: Vec<TypeId>,
+    resources: Vec<RwLock<BoxedResource>>,
+}
+
+/// Shared borrow of the store resource
+pub struct ResourceRef<'a, R> {
+    guard: RwLockReadGuard<'a, BoxedResource>,
+    _marker: PhantomData<&'a R>,
+}
+
+/// Exclusive borrow of the store resource
+pub struct ResourceMut<'a, R> {
+    guard: RwLockWriteGuard<'a, BoxedResource>,
+    _marker: PhantomData<&'a mut R>,
+}
+
+impl ResourcesContainer {
+    pub fn new() -> Self {
+        Self {
+            type_ids: Vec::new(),
+            resources: Vec::new(),
+        }
+    }
+
+    /// Inserts the resource and returns the replaced one of the same type
+    pub fn insert<R: Send + Sync + 'static>(&mut self, resource: R) -> Option<R> {
+        let type_id = TypeId::of::<R>();
+        match self.type_ids.binary_search(&type_id) {
+            Ok(i) => {
+                let slot = self.resources[i]
+                    .get_mut()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                let previous = std::mem::replace(slot, Box::new(resource));
+                Some(Self::unbox(previous))
+            }
+            Err(i) => {
+                self.type_ids.insert(i, type_id);
+                self.resources.insert(i, RwLock::new(Box::new(resource)));
+                None
+            }
+        }
+    }
+
+    pub fn remove<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        let i = self.type_ids.binary_search(&TypeId::of::<R>()).ok()?;
+        self.type_ids.remove(i);
+        let resource = self.resources.remove(i);
+
+        Some(Self::unbox(
+            resource
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        ))
+    }
+
+    #[inline]
+    pub fn contains<R: 'static>(&self) -> bool {
+        self.type_ids.binary_search(&TypeId::of::<R>()).is_ok()
+    }
+
+    /// Panics, if the resource is borrowed mutably
+    pub fn get<R: Send + Sync + 'static>(&self) -> Option<ResourceRef<'_, R>> {
+        let lock = self.get_lock::<R>()?;
+        let guard = match lock.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => Self::borrow_conflict::<R>(),
+        };
+
+        Some(ResourceRef {
+            guard,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Panics, if the resource is already borrowed
+    pub fn get_mut<R: Send + Sync + 'static>(&self) -> Option<ResourceMut<'_, R>> {
+        let lock = self.get_lock::<R>()?;
+        let guard = match lock.try_write() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => Self::borrow_conflict::<R>(),
+        };
+
+        Some(ResourceMut {
+            guard,
+            _marker: PhantomData,
+        })
+    }
+
+    fn get_lock<R: 'static>(&self) -> Option<&RwLock<BoxedResource>> {
+        self.type_ids
+            .binary_search(&TypeId::of::<R>())
+            .ok()
+            .map(|i| &self.resources[i])
+    }
+
+    fn unbox<R: 'static>(resource: BoxedResource) -> R {
+        unsafe { *resource.downcast::<R>().unwrap_unchecked() }
+    }
+
+    fn borrow_conflict<R>() -> ! {
+        panic!(
+            "Resource {} is already borrowed with the conflicting access",
+            std::any::type_name::<R>()
+        )
+    }
+}
+
+impl<'a, R: 'static> Deref for ResourceRef<'a, R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.guard.downcast_ref().unwrap_unchecked() }
+    }
+}
+
+impl<'a, R: 'static> Deref for ResourceMut<'a, R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.guard.downcast_ref().unwrap_unchecked() }
+    }
+}
+
+impl<'a, R: 'static> DerefMut for ResourceMut<'a, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.guard.downcast_mut().unwrap_unchecked() }
+    }
+}
+
+impl Store {
+    /// Inserts the global resource of the type and returns the replaced one
+    pub fn insert_resource<R: Send + Sync + 'static>(
+        &mut self,
+        resource: R,
+    ) -> Option<R> {
+        self.resources_container.insert(resource)
+    }
+
+    pub fn remove_resource<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        self.resources_container.remove()
+    }
+
+    #[inline]
+    pub fn contains_resource<R: Send + Sync + 'static>(&self) -> bool {
+        self.resources_container.contains::<R>()
+    }
+
+    /// Borrows the resource with the shared store reference, e.g. inside the system
+    /// or while the query is iterated. Panics, if the resource is borrowed mutably
+    pub fn resource<R: Send + Sync + 'static>(&self) -> Option<ResourceRef<'_, R>> {
+        self.resources_container.get()
+    }
+
+    /// Borrows the resource mutably with the shared store reference.
+    /// Panics, if the resource is already borrowed
+    pub fn resource_mut<R: Send + Sync + 'static>(&self) -> Option<ResourceMut<'_, R>> {
+        self.resources_container.get_mut()
+    }
+}
@@ -0,0 +1,478 @@
+use std::{any::TypeId, cell::RefCell};
+
+use crate::{
+    component_tuple::ComponentTuple,
+    query::{
+        access::ComponentQueryAccess,
+        component::{ComponentQuery, ComponentsQueryIter},
+        entity_component::{EntityComponentQuery, EntityComponentQueryIter},
+        ordered_component::{OrderedComponentQuery, OrderedComponentQueryIter},
+    },
+    CommandBuffer, ComponentId, Entity, Events, ResourceMut, ResourceRef, Store, Tag,
+};
+
+type SystemFn = Box<dyn FnMut(&SystemContext) + Send>;
+
+/// Components and resources, that are read and written by a system, and events,
+/// that are read by it
+#[derive(Debug, Clone, Default)]
+pub struct SystemAccess {
+    reads: Vec<ComponentId>,
+    writes: Vec<ComponentId>,
+    resource_reads: Vec<TypeId>,
+    resource_writes: Vec<TypeId>,
+    event_reads: Vec<TypeId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemId(usize);
+
+/// Runs registered systems in stages. Systems of the same stage don't conflict
+/// by the components and resources access and are executed in parallel. Conflicting systems
+/// keep the registration order unless it's changed with the explicit constraints.
+/// Events, sent by the systems, are added to the store after their stage
+pub struct Schedule {
+    systems: Vec<SystemFn>,
+    accesses: Vec<SystemAccess>,
+    /// Deferred commands of each system with its sent events, applied after the stage
+    command_buffers: Vec<CommandBuffer>,
+    orders: Vec<(SystemId, SystemId)>,
+    stages: Vec<Vec<SystemId>>,
+    is_stages_dirty: bool,
+}
+
+/// Store access of the running system. Queries and resources are checked against
+/// the declared system access, so systems of the same stage never access the same
+/// components or resources with write access
+pub struct SystemContext<'a> {
+    store: &'a Store,
+    access: &'a SystemAccess,
+    commands: RefCell<&'a mut CommandBuffer>,
+}
+
+// The store isn't `Sync` because of the raw pointers of its containers and the lifecycle
+// hooks, which aren't `Send`. The context is sent to the stage thread with the shared
+// store, but it never gives out `&Store`, and every context method is data-race free:
+// - `is_alive` and the queries read the entity and archetype containers, which are
+//   changed only with `&mut Store`, so they are immutable while the stage runs
+// - the queries increment the store change tick and mark the page ticks, which are atomics
+// - the queries write only the components of the declared write access, and the systems
+//   of the same stage don't declare the conflicting accesses
+// - the resources are `RwLock`s of `Send + Sync` values, locked by the declared access
+// - the events queues of `Send + Sync` events are changed only between the stages
+// The lifecycle hooks are called by the structural changes only, which need `&mut Store`.
+// The context isn't `Sync`, so the system can't share it with other threads
+unsafe impl<'a> Send for SystemContext<'a> {}
+
+impl SystemAccess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn of<A: ComponentQueryAccess>() -> Self {
+        Self::new().with::<A>()
+    }
+
+    /// Adds the query access to the system access. Used for the systems with
+    /// several queries
+    pub fn with<A: ComponentQueryAccess>(mut self) -> Self {
+        for id in A::read_type_ids() {
+            if !self.reads.contains(&id) {
+                self.reads.push(id);
+            }
+        }
+
+        for id in A::write_type_ids() {
+            if !self.writes.contains(&id) {
+                self.writes.push(id);
+            }
+        }
+
+        self
+    }
+
+    /// Adds the shared access to the resource
+    pub fn read_resource<R: Send + Sync + 'static>(mut self) -> Self {
+        let id = TypeId::of::<R>();
+        if !self.resource_reads.contains(&id) {
+            self.resource_reads.push(id);
+        }
+
+        self
+    }
+
+    /// Adds the exclusive access to the resource
+    pub fn write_resource<R: Send + Sync + 'static>(mut self) -> Self {
+        let id = TypeId::of::<R>();
+        if !self.resource_writes.contains(&id) {
+            self.resource_writes.push(id);
+        }
+
+        self
+    }
+
+    /// Adds the read of the events queue. Queues are changed only between the stages,
+    /// so the events reads never conflict
+    pub fn read_events<T: Send + Sync + 'static>(mut self) -> Self {
+        let id = TypeId::of::<T>();
+        if !self.event_reads.contains(&id) {
+            self.event_reads.push(id);
+        }
+
+        self
+    }
+
+    #[inline]
+    pub fn reads(&self) -> &[ComponentId] {
+        &self.reads
+    }
+
+    #[inline]
+    pub fn writes(&self) -> &[ComponentId] {
+        &self.writes
+    }
+
+    #[inline]
+    pub fn resource_reads(&self) -> &[TypeId] {
+        &self.resource_reads
+    }
+
+    #[inline]
+    pub fn resource_writes(&self) -> &[TypeId] {
+        &self.resource_writes
+    }
+
+    #[inline]
+    pub fn event_reads(&self) -> &[TypeId] {
+        &self.event_reads
+    }
+
+    pub fn is_conflict_with(&self, other: &SystemAccess) -> bool {
+        Self::is_sets_conflict(&self.reads, &self.writes, &other.reads, &other.writes)
+            || Self::is_sets_conflict(
+                &self.resource_reads,
+                &self.resource_writes,
+                &other.resource_reads,
+                &other.resource_writes,
+            )
+    }
+
+    fn is_sets_conflict<T: PartialEq>(
+        reads: &[T],
+        writes: &[T],
+        other_reads: &[T],
+        other_writes: &[T],
+    ) -> bool {
+        writes
+            .iter()
+            .any(|id| other_writes.contains(id) || other_reads.contains(id))
+            || other_writes.iter().any(|id| reads.contains(id))
+    }
+
+    fn assert_query<A: ComponentQueryAccess>(&self) {
+        let is_declared = A::read_type_ids()
+            .iter()
+            .all(|id| self.reads.contains(id) || self.writes.contains(id))
+            && A::write_type_ids()
+                .iter()
+                .all(|id| self.writes.contains(id));
+
+        assert!(
+            is_declared,
+            "Query access {} isn't declared by the system",
+            std::any::type_name::<A>()
+        );
+    }
+}
+
+impl<'a> SystemContext<'a> {
+    #[inline]
+    pub fn access(&self) -> &SystemAccess {
+        self.access
+    }
+
+    #[inline]
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.store.is_alive(entity)
+    }
+
+    /// See [`Store::component_query_iter`]. Panics, if the query access isn't declared
+    pub fn component_query_iter<'b, T: ComponentQueryAccess>(
+        &'b self,
+        query: &'b mut ComponentQuery<T>,
+    ) -> ComponentsQueryIter<'b, T>
+    where
+        T::AccessOutput<'b>: Send,
+    {
+        self.access.assert_query::<T>();
+        self.store.component_query_iter(query)
+    }
+
+    /// See [`Store::entity_component_query_iter`]. Panics, if the query access
+    /// isn't declared
+    pub fn entity_component_query_iter<'b, T: ComponentQueryAccess>(
+        &'b self,
+        entities: &'b [Entity],
+        query: &'b mut EntityComponentQuery<T>,
+    ) -> EntityComponentQueryIter<'b, T>
+    where
+        T::AccessOutput<'b>: Send,
+    {
+        self.access.assert_query::<T>();
+        self.store.entity_component_query_iter(entities, query)
+    }
+
+    /// See [`Store::ordered_query_iter`]. Panics, if the query access isn't declared
+    pub fn ordered_query_iter<'b, O: Tag, T: ComponentQueryAccess>(
+        &'b self,
+        query: &'b mut OrderedComponentQuery<O, T>,
+    ) -> OrderedComponentQueryIter<'b, T>
+    where
+        T::AccessOutput<'b>: Send,
+    {
+        self.access.assert_query::<T>();
+        self.store.ordered_query_iter(query)
+    }
+
+    /// See [`Store::get_components_refs`]. Panics, if the components read
+    /// isn't declared
+    pub fn get_components_refs<T: ComponentTuple>(
+        &self,
+        entity: Entity,
+    ) -> Option<T::RefTuple<'_>>
+    where
+        for<'b> T::RefTuple<'b>: Send,
+    {
+        let is_declared = T::type_ids()
+            .iter()
+            .all(|id| self.access.reads.contains(id) || self.access.writes.contains(id));
+        assert!(
+            is_declared,
+            "Components {} read isn't declared by the system",
+            std::any::type_name::<T>()
+        );
+
+        self.store.get_components_refs::<T>(entity)
+    }
+
+    /// See [`Store::resource`]. Panics, if the resource access isn't declared
+    pub fn resource<R: Send + Sync + 'static>(&self) -> Option<ResourceRef<'_, R>> {
+        let id = TypeId::of::<R>();
+        assert!(
+            self.access.resource_reads.contains(&id)
+                || self.access.resource_writes.contains(&id),
+            "Resource {} read isn't declared by the system",
+            std::any::type_name::<R>()
+        );
+
+        self.store.resource::<R>()
+    }
+
+    /// See [`Store::resource_mut`]. Panics, if the resource write isn't declared
+    pub fn resource_mut<R: Send + Sync + 'static>(&self) -> Option<ResourceMut<'_, R>> {
+        assert!(
+            self.access.resource_writes.contains(&TypeId::of::<R>()),
+            "Resource {} write isn't declared by the system",
+            std::any::type_name::<R>()
+        );
+
+        self.store.resource_mut::<R>()
+    }
+
+    /// Events queue of the store. Events, sent by the systems of the current stage,
+    /// aren't in the queue yet. Panics, if the events read isn't declared
+    pub fn events<T: Send + Sync + 'static>(&self) -> Option<&Events<T>> {
+        assert!(
+            self.access.event_reads.contains(&TypeId::of::<T>()),
+            "Events {} read isn't declared by the system",
+            std::any::type_name::<T>()
+        );
+
+        self.store.events::<T>()
+    }
+
+    /// Sends the event to the store queue after the system stage. Events of the
+    /// stage are sent in the systems registration order
+    pub fn send_event<T: Send + Sync + 'static>(&self, event: T) {
+        self.commands.borrow_mut().send_event(event);
+    }
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self {
+            systems: Vec::new(),
+            accesses: Vec::new(),
+            command_buffers: Vec::new(),
+            orders: Vec::new(),
+            stages: Vec::new(),
+            is_stages_dirty: false,
+        }
+    }
+
+    pub fn add_system<A: ComponentQueryAccess>(
+        &mut self,
+        system: impl FnMut(&SystemContext) + Send + 'static,
+    ) -> SystemId {
+        self.add_system_with_access(SystemAccess::of::<A>(), system)
+    }
+
+    pub fn add_system_with_access(
+        &mut self,
+        access: SystemAccess,
+        system: impl FnMut(&SystemContext) + Send + 'static,
+    ) -> SystemId {
+        self.systems.push(Box::new(system));
+        self.accesses.push(access);
+        self.command_buffers.push(CommandBuffer::new());
+        self.is_stages_dirty = true;
+
+        SystemId(self.systems.len() - 1)
+    }
+
+    /// Makes `system` run in a stage before the `other` system stage
+    pub fn run_before(&mut self, system: SystemId, other: SystemId) {
+        self.orders.push((system, other));
+        self.is_stages_dirty = true;
+    }
+
+    /// Makes `system` run in a stage after the `other` system stage
+    pub fn run_after(&mut self, system: SystemId, other: SystemId) {
+        self.run_before(other, system);
+    }
+
+    /// Returns systems grouped by the stages in the execution order
+    pub fn stages(&mut self) -> &[Vec<SystemId>] {
+        if self.is_stages_dirty {
+            self.build_stages();
+        }
+
+        &self.stages
+    }
+
+    pub fn run(&mut self, store: &mut Store) {
+        if self.is_stages_dirty {
+            self.build_stages();
+        }
+
+        for stage in &self.stages {
+            Self::run_stage(
+                stage,
+                store,
+                &mut self.systems,
+                &self.accesses,
+                &mut self.command_buffers,
+            );
+
+            for &SystemId(system) in stage {
+                store.apply_commands(&mut self.command_buffers[system]);
+            }
+        }
+    }
+
+    fn run_stage(
+        stage: &[SystemId],
+        store: &Store,
+        systems: &mut [SystemFn],
+        accesses: &[SystemAccess],
+        command_buffers: &mut [CommandBuffer],
+    ) {
+        if stage.len() == 1 {
+            let system = stage[0].0;
+            let context = SystemContext {
+                store,
+                access: &accesses[system],
+                commands: RefCell::new(&mut command_buffers[system]),
+            };
+            (systems[system])(&context);
+            return;
+        }
+
+        std::thread::scope(|scope| {
+            let stage_systems = systems
+                .iter_mut()
+                .zip(accesses)
+                .zip(command_buffers.iter_mut())
+                .enumerate()
+                .filter(|(i, _)| stage.contains(&SystemId(*i)));
+
+            for (_, ((system, access), commands)) in stage_systems {
+                let context = SystemContext {
+                    store,
+                    access,
+                    commands: RefCell::new(commands),
+                };
+                scope.spawn(move || system(&context));
+            }
+        });
+    }
+
+    fn build_stages(&mut self) {
+        let systems_count = self.systems.len();
+        let sorted = self.sort_by_orders();
+
+        let mut system_to_stage = vec![0; systems_count];
+        let mut stages_count = 0;
+
+        for (sorted_idx, &system) in sorted.iter().enumerate() {
+            let mut stage = 0;
+
+            for &previous in &sorted[..sorted_idx] {
+                let is_ordered = self
+                    .orders
+                    .contains(&(SystemId(previous), SystemId(system)));
+                let is_conflict =
+                    self.accesses[previous].is_conflict_with(&self.accesses[system]);
+
+                if is_ordered || is_conflict {
+                    stage = stage.max(system_to_stage[previous] + 1);
+                }
+            }
+
+            system_to_stage[system] = stage;
+            stages_count = stages_count.max(stage + 1);
+        }
+
+        self.stages = vec![Vec::new(); stages_count];
+        for system in sorted {
+            self.stages[system_to_stage[system]].push(SystemId(system));
+        }
+
+        self.is_stages_dirty = false;
+    }
+
+    /// Topological sort by the explicit orders. Unordered systems keep registration order
+    fn sort_by_orders(&self) -> Vec<usize> {
+        let systems_count = self.systems.len();
+        let mut predecessors_count = vec![0; systems_count];
+        for &(_, after) in &self.orders {
+            predecessors_count[after.0] += 1;
+        }
+
+        let mut is_sorted = vec![false; systems_count];
+        let mut sorted = Vec::with_capacity(systems_count);
+
+        while sorted.len() < systems_count {
+            let next = (0..systems_count)
+                .find(|&i| !is_sorted[i] && predecessors_count[i] == 0)
+                .expect("Systems order constraints contain a cycle");
+
+            is_sorted[next] = true;
+            sorted.push(next);
+
+            for &(before, after) in &self.orders {
+                if before.0 == next {
+                    predecessors_count[after.0] -= 1;
+                }
+            }
+        }
+
+        sorted
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
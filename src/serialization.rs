@@ -0,0 +1,942 @@
+use std::{any::TypeId, fmt};
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    component_type::ComponentType, Archetype, ArchetypeBuilder, Component, ComponentId,
+    Entity, Store, Tag,
+};
+
+type SerializeComponentFn = fn(*const u8) -> *const dyn erased_serde::Serialize;
+type DeserializeComponentFn = for<'de> unsafe fn(
+    &mut dyn erased_serde::Deserializer<'de>,
+    *mut u8,
+) -> Result<(), erased_serde::Error>;
+type CollectTaggedFn = fn(&Store) -> Vec<u32>;
+type AddTagFn = fn(&mut Store, Entity);
+
+/// Version of the serialized store layout. Increments on the incompatible changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Maps component, tag and order group types to the stable names. The names are
+/// written instead of `TypeId`, because it changes between builds
+pub struct SerializationRegistry {
+    components: Vec<ComponentEntry>,
+    tags: Vec<TagEntry>,
+    order_groups: Vec<TagEntry>,
+}
+
+struct ComponentEntry {
+    name: &'static str,
+    component_type: ComponentType,
+    serialize_fn: SerializeComponentFn,
+    deserialize_fn: DeserializeComponentFn,
+}
+
+struct TagEntry {
+    name: &'static str,
+    type_id: TypeId,
+    /// Archetype-stored tags aren't written as components. They are added back
+    /// with the tags, which moves the entities to their archetypes
+    is_archetype_tag: bool,
+    collect_fn: CollectTaggedFn,
+    add_fn: AddTagFn,
+}
+
+/// Store with the registry, that can be passed to any serde serializer
+pub struct SerializableStore<'a> {
+    store: &'a Store,
+    registry: &'a SerializationRegistry,
+}
+
+/// Deserializes the store, written by [`SerializableStore`]
+pub struct StoreSeed<'a> {
+    registry: &'a SerializationRegistry,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EntitiesData {
+    versions: Vec<u32>,
+    alive: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NamedEntities {
+    name: String,
+    entities: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum StoreField {
+    FormatVersion,
+    Entities,
+    Archetypes,
+    Tags,
+    OrderGroups,
+    Hierarchy,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum ArchetypeField {
+    Components,
+    Entities,
+}
+
+const STORE_FIELDS: &[&str] = &[
+    "format_version",
+    "entities",
+    "archetypes",
+    "tags",
+    "order_groups",
+    "hierarchy",
+];
+const ARCHETYPE_FIELDS: &[&str] = &["components", "entities"];
+
+impl SerializationRegistry {
+    pub fn new() -> Self {
+        Self {
+            components: Vec::new(),
+            tags: Vec::new(),
+            order_groups: Vec::new(),
+        }
+    }
+
+    pub fn register_component<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + Serialize + DeserializeOwned + 'static,
+    {
+        let component_type = ComponentType::of::<T>();
+        assert!(
+            self.components
+                .iter()
+                .all(|e| e.name != name && e.component_type.id() != component_type.id()),
+            "Component {} or name {name} is already registered",
+            component_type.name()
+        );
+
+        self.components.push(ComponentEntry {
+            name,
+            component_type,
+            serialize_fn: as_erased_serialize::<T>,
+            deserialize_fn: deserialize_component::<T>,
+        });
+
+        self
+    }
+
+    pub fn register_tag<T: Tag>(&mut self, name: &'static str) -> &mut Self {
+        Self::push_tag_entry(
+            &mut self.tags,
+            TagEntry {
+                name,
+                type_id: TypeId::of::<T>(),
+                is_archetype_tag: T::archetype_component().is_some(),
+                collect_fn: collect_tagged::<T>,
+                add_fn: add_tag::<T>,
+            },
+        );
+
+        self
+    }
+
+    pub fn register_order_group<T: Tag>(&mut self, name: &'static str) -> &mut Self {
+        Self::push_tag_entry(
+            &mut self.order_groups,
+            TagEntry {
+                name,
+                type_id: TypeId::of::<T>(),
+                is_archetype_tag: false,
+                collect_fn: collect_ordered::<T>,
+                add_fn: add_ordered::<T>,
+            },
+        );
+
+        self
+    }
+
+    pub fn store_seed(&self) -> StoreSeed<'_> {
+        StoreSeed { registry: self }
+    }
+
+    fn push_tag_entry(entries: &mut Vec<TagEntry>, entry: TagEntry) {
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.name != entry.name && e.type_id != entry.type_id),
+            "Tag or name {} is already registered",
+            entry.name
+        );
+
+        entries.push(entry);
+    }
+
+    fn is_archetype_tag(&self, id: ComponentId) -> bool {
+        self.tags
+            .iter()
+            .any(|e| e.is_archetype_tag && ComponentId::Type(e.type_id) == id)
+    }
+
+    fn find_component_by_id(&self, id: ComponentId) -> Option<&ComponentEntry> {
+        self.components.iter().find(|e| e.component_type.id() == id)
+    }
+
+    fn find_component_by_name(&self, name: &str) -> Option<&ComponentEntry> {
+        self.components.iter().find(|e| e.name == name)
+    }
+}
+
+impl Default for SerializationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Store {
+    /// Wraps the store to serialize it with the names of the registry.
+    /// All components of the store must be registered
+    pub fn serializable<'a>(
+        &'a self,
+        registry: &'a SerializationRegistry,
+    ) -> SerializableStore<'a> {
+        SerializableStore {
+            store: self,
+            registry,
+        }
+    }
+
+    /// Creates the store from the data, written by [`Store::serializable`].
+    /// Entities keep their ids and versions
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        registry: &SerializationRegistry,
+        deserializer: D,
+    ) -> Result<Store, D::Error> {
+        registry.store_seed().deserialize(deserializer)
+    }
+}
+
+fn as_erased_serialize<T: Serialize + 'static>(
+    ptr: *const u8,
+) -> *const dyn erased_serde::Serialize {
+    ptr as *const T as *const dyn erased_serde::Serialize
+}
+
+/// # Safety
+/// `dst` must be valid for the write of `T`
+unsafe fn deserialize_component<'de, T: DeserializeOwned>(
+    deserializer: &mut dyn erased_serde::Deserializer<'de>,
+    dst: *mut u8,
+) -> Result<(), erased_serde::Error> {
+    let value: T = erased_serde::deserialize(deserializer)?;
+    (dst as *mut T).write(value);
+
+    Ok(())
+}
+
+fn collect_tagged<T: Tag>(store: &Store) -> Vec<u32> {
+    (0..store.entities_capacity() as u32)
+        .filter(|&id| {
+            store.entities_container.is_alive_at_index(id as usize)
+                && store.tag_container.has_tag::<T>(id)
+        })
+        .collect()
+}
+
+fn add_tag<T: Tag>(store: &mut Store, entity: Entity) {
+    store.add_tag::<T>(entity);
+}
+
+fn collect_ordered<T: Tag>(store: &Store) -> Vec<u32> {
+    let mut ids = Vec::new();
+    let mut current = store.get_first_entity_ordered_by::<T>();
+    while let Some(entity) = current {
+        ids.push(entity.id);
+        current = store.get_next_entity_ordered_by::<T>(entity);
+    }
+
+    ids
+}
+
+fn add_ordered<T: Tag>(store: &mut Store, entity: Entity) {
+    store.add_entity_order_by::<T>(entity);
+}
+
+impl<'a> Serialize for SerializableStore<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Store", STORE_FIELDS.len())?;
+        state.serialize_field("format_version", &FORMAT_VERSION)?;
+        state.serialize_field("entities", &self.entities_data())?;
+        state.serialize_field("archetypes", &ArchetypesData(self))?;
+        state.serialize_field(
+            "tags",
+            &Self::named_entities(self.store, &self.registry.tags),
+        )?;
+        state.serialize_field(
+            "order_groups",
+            &Self::named_entities(self.store, &self.registry.order_groups),
+        )?;
+        state.serialize_field("hierarchy", &self.hierarchy_links())?;
+        state.end()
+    }
+}
+
+impl<'a> SerializableStore<'a> {
+    fn entities_data(&self) -> EntitiesData {
+        let entities = &self.store.entities_container;
+        let versions = unsafe {
+            std::slice::from_raw_parts(entities.entity_versions(), entities.capacity())
+        };
+
+        // Versions of the dead ids are kept, so the old entities stay invalid after load
+        let used_ids_count = versions.iter().rposition(|&v| v != 0).map_or(0, |i| i + 1);
+
+        EntitiesData {
+            versions: versions[..used_ids_count].to_vec(),
+            alive: (0..used_ids_count as u32)
+                .filter(|&id| entities.is_alive_at_index(id as usize))
+                .collect(),
+        }
+    }
+
+    /// Child-parent pairs in the depth-first order, so the children order is kept on load
+    fn hierarchy_links(&self) -> Vec<(u32, u32)> {
+        let store = self.store;
+        let hierarchy = &store.hierarchy_container;
+        let mut links = Vec::new();
+
+        for id in 0..store.entities_capacity() as u32 {
+            let is_root = store.entities_container.is_alive_at_index(id as usize)
+                && hierarchy.get_parent_id(id).is_none()
+                && hierarchy.get_first_child_id(id).is_some();
+            if !is_root {
+                continue;
+            }
+
+            for descendant in hierarchy.collect_descendant_ids(id) {
+                let parent =
+                    unsafe { hierarchy.get_parent_id(descendant).unwrap_unchecked() };
+                links.push((descendant, parent));
+            }
+        }
+
+        links
+    }
+
+    fn named_entities(store: &Store, entries: &[TagEntry]) -> Vec<NamedEntities> {
+        entries
+            .iter()
+            .map(|e| NamedEntities {
+                name: e.name.to_owned(),
+                entities: (e.collect_fn)(store),
+            })
+            .filter(|n| !n.entities.is_empty())
+            .collect()
+    }
+}
+
+struct ArchetypesData<'a, 'b>(&'b SerializableStore<'a>);
+
+struct ArchetypeData<'a> {
+    store: &'a Store,
+    archetype_index: usize,
+    entries: Vec<&'a ComponentEntry>,
+}
+
+struct EntityRows<'a, 'b>(&'b ArchetypeData<'a>);
+
+struct EntityRow<'a, 'b> {
+    archetype_data: &'b ArchetypeData<'a>,
+    page_index: usize,
+    index_in_page: usize,
+}
+
+struct ErasedComponent<'a> {
+    entry: &'a ComponentEntry,
+    ptr: *const u8,
+}
+
+impl<'a, 'b> Serialize for ArchetypesData<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SerializableStore { store, registry } = *self.0;
+        let container = &store.archetypes_container;
+        let pages = container.get_pages();
+
+        let mut archetypes = Vec::new();
+        for (archetype_index, archetype) in container.get_archetypes().iter().enumerate()
+        {
+            let is_empty = container
+                .get_archetype_page_indices(archetype_index)
+                .iter()
+                .all(|&p| pages[p].entity_count() == 0);
+            if is_empty {
+                continue;
+            }
+
+            let entries = archetype
+                .components_iter()
+                .filter(|c| !registry.is_archetype_tag(c.id()))
+                .map(|c| {
+                    registry.find_component_by_id(c.id()).ok_or_else(|| {
+                        ser::Error::custom(format!(
+                            "Component {} isn't registered",
+                            c.name()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, S::Error>>()?;
+            if entries.is_empty() {
+                continue;
+            }
+
+            archetypes.push(ArchetypeData {
+                store,
+                archetype_index,
+                entries,
+            });
+        }
+
+        serializer.collect_seq(archetypes)
+    }
+}
+
+impl<'a> Serialize for ArchetypeData<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names: Vec<_> = self.entries.iter().map(|e| e.name).collect();
+
+        let mut state =
+            serializer.serialize_struct("Archetype", ARCHETYPE_FIELDS.len())?;
+        state.serialize_field("components", &names)?;
+        state.serialize_field("entities", &EntityRows(self))?;
+        state.end()
+    }
+}
+
+impl<'a, 'b> Serialize for EntityRows<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let container = &self.0.store.archetypes_container;
+        let pages = container.get_pages();
+
+        let rows = container
+            .get_archetype_page_indices(self.0.archetype_index)
+            .iter()
+            .flat_map(|&page_index| {
+                (0..pages[page_index].entity_count()).map(move |index_in_page| {
+                    EntityRow {
+                        archetype_data: self.0,
+                        page_index,
+                        index_in_page,
+                    }
+                })
+            });
+
+        serializer.collect_seq(rows)
+    }
+}
+
+impl<'a, 'b> Serialize for EntityRow<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries = &self.archetype_data.entries;
+        let view = unsafe {
+            self.archetype_data
+                .store
+                .archetypes_container
+                .get_page_view_unchecked(self.page_index)
+        };
+
+        let mut state = serializer.serialize_tuple(entries.len() + 1)?;
+        let entity_id = unsafe { *view.page.entity_id_ptrs().add(self.index_in_page) };
+        state.serialize_element(&entity_id)?;
+
+        for &entry in entries {
+            let component_index = unsafe {
+                view.archetype
+                    .find_component_index(entry.component_type.id())
+                    .unwrap_unchecked()
+            };
+            let ptr = unsafe {
+                view.page.get_component_data_ptr(
+                    self.index_in_page,
+                    *view.layout.component_offsets().add(component_index),
+                    entry.component_type.size(),
+                )
+            };
+
+            state.serialize_element(&ErasedComponent { entry, ptr })?;
+        }
+
+        state.end()
+    }
+}
+
+impl<'a> Serialize for ErasedComponent<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        unsafe { &*(self.entry.serialize_fn)(self.ptr) }.serialize(serializer)
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for StoreSeed<'a> {
+    type Value = Store;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Store, D::Error> {
+        deserializer.deserialize_struct("Store", STORE_FIELDS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for StoreSeed<'a> {
+    type Value = Store;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("serialized store")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Store, A::Error> {
+        let format_version = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        check_format_version(format_version)?;
+
+        let entities = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let mut store = restore_entities(&entities)?;
+
+        seq.next_element_seed(ArchetypesSeed {
+            registry: self.registry,
+            store: &mut store,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+        let tags: Vec<NamedEntities> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        restore_named_entities(&mut store, &self.registry.tags, &tags)?;
+
+        let order_groups: Vec<NamedEntities> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+        restore_named_entities(&mut store, &self.registry.order_groups, &order_groups)?;
+
+        let hierarchy: Vec<(u32, u32)> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(5, &self))?;
+        restore_hierarchy(&mut store, &hierarchy)?;
+
+        Ok(store)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Store, A::Error> {
+        let mut store = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                StoreField::FormatVersion => check_format_version(map.next_value()?)?,
+                StoreField::Entities => {
+                    if store.is_some() {
+                        return Err(de::Error::duplicate_field("entities"));
+                    }
+
+                    store = Some(restore_entities(&map.next_value()?)?);
+                }
+                StoreField::Archetypes => {
+                    map.next_value_seed(ArchetypesSeed {
+                        registry: self.registry,
+                        store: restored_store(&mut store)?,
+                    })?;
+                }
+                StoreField::Tags => {
+                    let tags: Vec<NamedEntities> = map.next_value()?;
+                    restore_named_entities(
+                        restored_store(&mut store)?,
+                        &self.registry.tags,
+                        &tags,
+                    )?;
+                }
+                StoreField::OrderGroups => {
+                    let order_groups: Vec<NamedEntities> = map.next_value()?;
+                    restore_named_entities(
+                        restored_store(&mut store)?,
+                        &self.registry.order_groups,
+                        &order_groups,
+                    )?;
+                }
+                StoreField::Hierarchy => {
+                    let hierarchy: Vec<(u32, u32)> = map.next_value()?;
+                    restore_hierarchy(restored_store(&mut store)?, &hierarchy)?;
+                }
+            }
+        }
+
+        store.ok_or_else(|| de::Error::missing_field("entities"))
+    }
+}
+
+fn check_format_version<E: de::Error>(format_version: u32) -> Result<(), E> {
+    if format_version == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(E::custom(format!(
+            "Unsupported store format version {format_version}, expected {FORMAT_VERSION}"
+        )))
+    }
+}
+
+/// Entities must be restored before the other store data, that refers them by ids
+fn restored_store<E: de::Error>(store: &mut Option<Store>) -> Result<&mut Store, E> {
+    store
+        .as_mut()
+        .ok_or_else(|| E::custom("entities must precede the other store data"))
+}
+
+fn restore_entities<E: de::Error>(data: &EntitiesData) -> Result<Store, E> {
+    for &id in &data.alive {
+        match data.versions.get(id as usize) {
+            Some(&version) if version != 0 => {}
+            _ => return Err(E::custom(format!("Alive entity {id} has no version"))),
+        }
+    }
+
+    let mut store = Store::with_capacity(data.versions.len().max(1));
+    store
+        .entities_container
+        .restore(&data.versions, &data.alive);
+
+    Ok(store)
+}
+
+fn restore_named_entities<E: de::Error>(
+    store: &mut Store,
+    entries: &[TagEntry],
+    data: &[NamedEntities],
+) -> Result<(), E> {
+    for named in data {
+        let entry = entries
+            .iter()
+            .find(|e| e.name == named.name)
+            .ok_or_else(|| E::custom(format!("Tag {} isn't registered", named.name)))?;
+
+        for &id in &named.entities {
+            let entity = alive_entity(store, id)?;
+            (entry.add_fn)(store, entity);
+        }
+    }
+
+    Ok(())
+}
+
+fn restore_hierarchy<E: de::Error>(
+    store: &mut Store,
+    links: &[(u32, u32)],
+) -> Result<(), E> {
+    for &(child_id, parent_id) in links {
+        alive_entity::<E>(store, child_id)?;
+        alive_entity::<E>(store, parent_id)?;
+
+        if store
+            .hierarchy_container
+            .is_descendant_or_self(parent_id, child_id)
+        {
+            return Err(E::custom(format!(
+                "Entity {parent_id} can't be a parent of its ancestor {child_id}"
+            )));
+        }
+
+        store.hierarchy_container.set_parent(child_id, parent_id);
+    }
+
+    Ok(())
+}
+
+fn alive_entity<E: de::Error>(store: &Store, id: u32) -> Result<Entity, E> {
+    let is_alive = store.entities_container.validate_id(id)
+        && store.entities_container.is_alive_at_index(id as usize);
+
+    if is_alive {
+        Ok(unsafe { store.get_entity_by_id_unchecked(id) })
+    } else {
+        Err(E::custom(format!("Entity {id} isn't alive")))
+    }
+}
+
+struct ArchetypesSeed<'a, 'b> {
+    registry: &'a SerializationRegistry,
+    store: &'b mut Store,
+}
+
+struct ArchetypeSeed<'a, 'b> {
+    registry: &'a SerializationRegistry,
+    store: &'b mut Store,
+}
+
+struct RowsSeed<'a, 'b> {
+    store: &'b mut Store,
+    archetype: &'b Archetype,
+    entries: &'b [&'a ComponentEntry],
+}
+
+struct RowSeed<'a, 'b> {
+    store: &'b mut Store,
+    archetype: &'b Archetype,
+    entries: &'b [&'a ComponentEntry],
+}
+
+struct ComponentSeed {
+    deserialize_fn: DeserializeComponentFn,
+    dst: *mut u8,
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for ArchetypesSeed<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for ArchetypesSeed<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence of archetypes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq
+            .next_element_seed(ArchetypeSeed {
+                registry: self.registry,
+                store: &mut *self.store,
+            })?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for ArchetypeSeed<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_struct("Archetype", ARCHETYPE_FIELDS, self)
+    }
+}
+
+impl<'a, 'b> ArchetypeSeed<'a, 'b> {
+    fn resolve_components<E: de::Error>(
+        &self,
+        names: &[String],
+    ) -> Result<(Archetype, Vec<&'a ComponentEntry>), E> {
+        let entries = names
+            .iter()
+            .map(|name| {
+                self.registry.find_component_by_name(name).ok_or_else(|| {
+                    E::custom(format!("Component {name} isn't registered"))
+                })
+            })
+            .collect::<Result<Vec<_>, E>>()?;
+
+        let component_types: Vec<_> = entries.iter().map(|e| e.component_type).collect();
+        let archetype = ArchetypeBuilder::new()
+            .include_component_types(&component_types)
+            .build();
+
+        if archetype.component_count() != entries.len() {
+            return Err(E::custom("Archetype components are duplicated"));
+        }
+
+        Ok((archetype, entries))
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for ArchetypeSeed<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("archetype with entities")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let names: Vec<String> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let (archetype, entries) = self.resolve_components(&names)?;
+
+        seq.next_element_seed(RowsSeed {
+            store: self.store,
+            archetype: &archetype,
+            entries: &entries,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(1, &"archetype with entities"))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut components = None;
+        let mut has_entities = false;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                ArchetypeField::Components => {
+                    let names: Vec<String> = map.next_value()?;
+                    components = Some(self.resolve_components(&names)?);
+                }
+                ArchetypeField::Entities => {
+                    let (archetype, entries) = components.as_ref().ok_or_else(|| {
+                        de::Error::custom("components must precede entities")
+                    })?;
+
+                    map.next_value_seed(RowsSeed {
+                        store: &mut *self.store,
+                        archetype,
+                        entries,
+                    })?;
+                    has_entities = true;
+                }
+            }
+        }
+
+        if components.is_none() {
+            return Err(de::Error::missing_field("components"));
+        }
+        if !has_entities {
+            return Err(de::Error::missing_field("entities"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for RowsSeed<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for RowsSeed<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("sequence of entity rows")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq
+            .next_element_seed(RowSeed {
+                store: &mut *self.store,
+                archetype: self.archetype,
+                entries: self.entries,
+            })?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de> DeserializeSeed<'de> for RowSeed<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_tuple(self.entries.len() + 1, self)
+    }
+}
+
+impl<'a, 'b> RowSeed<'a, 'b> {
+    /// Drops already deserialized components and removes the entity from the archetype,
+    /// so the store never contains uninitialized components
+    unsafe fn rollback(&mut self, entity_id: u32, dsts: &[*mut u8]) {
+        for (entry, &dst) in self.entries.iter().zip(dsts) {
+            if let Some(drop_fn) = entry.component_type.drop_fn() {
+                drop_fn(dst);
+            }
+        }
+
+        self.store
+            .detach_entity_from_archetype_without_drop(entity_id);
+    }
+}
+
+impl<'a, 'b, 'de> Visitor<'de> for RowSeed<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "entity id with {} components",
+            self.entries.len()
+        )
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let entity_id: u32 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        alive_entity::<A::Error>(self.store, entity_id)?;
+        if unsafe { self.store.has_archetype_unchecked(entity_id) } {
+            return Err(de::Error::custom(format!(
+                "Entity {entity_id} is placed in several archetypes"
+            )));
+        }
+
+        let entity_in_arch = self
+            .store
+            .attach_entity_to_archetype(entity_id, self.archetype);
+
+        let mut dsts = Vec::with_capacity(self.entries.len());
+        for (i, entry) in self.entries.iter().enumerate() {
+            let dst = unsafe {
+                let view = self
+                    .store
+                    .archetypes_container
+                    .get_page_view_unchecked(entity_in_arch.page_index as usize);
+                let component_index = view
+                    .archetype
+                    .find_component_index(entry.component_type.id())
+                    .unwrap_unchecked();
+
+                view.page.get_component_data_ptr_mut(
+                    entity_in_arch.index_in_page as usize,
+                    *view.layout.component_offsets().add(component_index),
+                    entry.component_type.size(),
+                )
+            };
+
+            let result = seq.next_element_seed(ComponentSeed {
+                deserialize_fn: entry.deserialize_fn,
+                dst,
+            });
+
+            match result {
+                Ok(Some(())) => dsts.push(dst),
+                Ok(None) => {
+                    unsafe { self.rollback(entity_id, &dsts) };
+                    return Err(de::Error::invalid_length(i + 1, &self));
+                }
+                Err(err) => {
+                    unsafe { self.rollback(entity_id, &dsts) };
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ComponentSeed {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        unsafe { (self.deserialize_fn)(&mut erased, self.dst) }.map_err(de::Error::custom)
+    }
+}
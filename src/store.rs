@@ -1,24 +1,55 @@
 use crate::{
-    archetype::Archetype, archetype_data_page::ArchetypeDataPage,
+    archetype::Archetype,
+    archetype_builder::ArchetypeBuilder,
+    archetype_data_page::{ArchetypeDataPage, SwapRemoveInfo},
     archetype_data_page_view::ArchetypeDataPageView,
-    archetypes_container::ArchetypesContainer, bitvec_utils,
-    component_tuple::ComponentTuple, entities_container::EntitiesContainer,
-    entity_in_archetype::EntityInArchetype, mem_utils,
-    order_group_container::OrderGroupContainer, tag_container::TagContainer,
-    ArchetypeBuilder, Entity,
+    archetypes_container::ArchetypesContainer,
+    bitvec_utils,
+    component_clone_fns::ComponentCloneFns,
+    component_tuple::ComponentTuple,
+    component_type::ComponentType,
+    entities_container::EntitiesContainer,
+    entity_in_archetype::EntityInArchetype,
+    events::EventsContainer,
+    hierarchy_container::HierarchyContainer,
+    lifecycle_hooks::LifecycleHooks,
+    mem_utils,
+    order_group_container::OrderGroupContainer,
+    reflection::ReflectionRegistry,
+    resources_container::ResourcesContainer,
+    store_config::StoreConfig,
+    tag_container::TagContainer,
+    Entity,
 };
 
-const ENTITIES_DEFAULT_CAPACITY: usize = 10;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+pub(crate) const ENTITIES_DEFAULT_CAPACITY: usize = 10;
 
 pub struct Store {
     pub(crate) entities_container: EntitiesContainer,
     pub(crate) archetypes_container: ArchetypesContainer,
     pub(crate) tag_container: TagContainer,
     pub(crate) order_group_container: OrderGroupContainer,
+    pub(crate) hierarchy_container: HierarchyContainer,
+    pub(crate) lifecycle_hooks: LifecycleHooks,
+    pub(crate) events_container: EventsContainer,
+    pub(crate) resources_container: ResourcesContainer,
+    pub(crate) component_clone_fns: ComponentCloneFns,
+    pub(crate) reflection_registry: ReflectionRegistry,
 
     entity_to_page: *mut u32,
     entity_to_index_in_page: *mut u32,
     entity_has_archetype_bit_vec: *mut u32,
+
+    change_tick: AtomicU32,
+}
+
+/// Removes the new entity with the uninitialized components, if writing them panics
+struct UninitEntityGuard<'a> {
+    store: &'a mut Store,
+    entity: Entity,
+    entity_in_arch: EntityInArchetype,
 }
 
 impl Store {
@@ -27,29 +58,60 @@ impl Store {
     }
 
     pub fn with_capacity(capacity: usize) -> Store {
+        Self::with_config(StoreConfig::new().entities_capacity(capacity))
+    }
+
+    pub fn with_config(config: StoreConfig) -> Store {
+        let capacity = config.entities_capacity;
         debug_assert!(capacity > 0, "Can't initialize empty store");
 
         Store {
             entities_container: EntitiesContainer::new(capacity),
-            archetypes_container: ArchetypesContainer::new(),
+            archetypes_container: ArchetypesContainer::new(&config),
             tag_container: TagContainer::new(capacity),
             order_group_container: OrderGroupContainer::new(capacity),
+            hierarchy_container: HierarchyContainer::new(capacity),
+            lifecycle_hooks: LifecycleHooks::default(),
+            events_container: EventsContainer::new(),
+            resources_container: ResourcesContainer::new(),
+            component_clone_fns: ComponentCloneFns::default(),
+            reflection_registry: ReflectionRegistry::default(),
 
             entity_has_archetype_bit_vec: unsafe { bitvec_utils::alloc(capacity) },
             entity_to_index_in_page: unsafe { mem_utils::alloc_zeroed(capacity) },
             entity_to_page: unsafe { mem_utils::alloc_zeroed(capacity) },
+
+            change_tick: AtomicU32::new(0),
         }
     }
 
+    /// Default size of the components data page, see [`StoreConfig::page_size`]
     pub const fn data_page_size() -> usize {
         ArchetypeDataPage::PAGE_SIZE_BYTES
     }
 
+    /// Size of the components data page of the archetype in this store
+    pub fn archetype_page_size(&self, archetype: &Archetype) -> usize {
+        self.archetypes_container.get_archetype_page_size(archetype)
+    }
+
     #[inline(always)]
     pub fn entities_capacity(&self) -> usize {
         self.entities_container.capacity()
     }
 
+    /// Current tick of the components changes. It increments on every components
+    /// write and query iteration
+    #[inline(always)]
+    pub fn change_tick(&self) -> u32 {
+        self.change_tick.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub(crate) fn increment_change_tick(&self) -> u32 {
+        self.change_tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
     pub fn create_entity(&mut self) -> Entity {
         let entity = self.register_new_entity();
 
@@ -60,19 +122,192 @@ impl Store {
         return entity;
     }
 
-    pub fn create_entity_with_archetype(&mut self, archetype: &Archetype) -> Entity {
+    /// Creates entity with uninitialized components of the archetype.
+    ///
+    /// # Safety
+    /// All components must be initialized (e.g. with `std::ptr::write` or by
+    /// [`Store::add_components`]) before they are read or the entity is dropped
+    /// with the store
+    pub unsafe fn create_entity_with_archetype(
+        &mut self,
+        archetype: &Archetype,
+    ) -> Entity {
         let entity = self.register_new_entity();
-        let entity_in_arch = self.archetypes_container.add_entity(entity.id, archetype);
+        self.attach_entity_to_archetype(entity.id, archetype);
+
+        entity
+    }
+
+    /// Places alive entity without archetype to the archetype with uninitialized components
+    pub(crate) fn attach_entity_to_archetype(
+        &mut self,
+        entity_id: u32,
+        archetype: &Archetype,
+    ) -> EntityInArchetype {
+        let tick = self.increment_change_tick();
+        let entity_in_arch = self
+            .archetypes_container
+            .add_entity(entity_id, archetype, tick);
+
+        unsafe {
+            self.set_page_index_unchecked(entity_id, entity_in_arch.page_index);
+            self.set_index_in_page_unchecked(entity_id, entity_in_arch.index_in_page);
+            self.enable_archetype_unchecked(entity_id)
+        }
+
+        entity_in_arch
+    }
+
+    /// Removes entity from its archetype without running drop glue of the components.
+    ///
+    /// # Safety
+    /// - Entity must have an archetype
+    /// - Components with drop glue must be dropped or moved out before the call
+    #[cfg(feature = "serde")]
+    pub(crate) unsafe fn detach_entity_from_archetype_without_drop(
+        &mut self,
+        entity_id: u32,
+    ) {
+        let entity_in_arch = self.get_entity_in_archetype_unchecked(entity_id);
+        let swap_remove = self
+            .archetypes_container
+            .swap_remove_moved_entity(entity_in_arch);
+
+        self.update_swap_removed_entity(entity_in_arch, swap_remove);
+        self.disable_archetype_unchecked(entity_id);
+    }
+
+    /// Creates entities with the components of the iterator. The archetype is reserved
+    /// once and the entities storage grows once by the iterator lower size bound.
+    /// Components are written to the free slots of the page at once, then the page
+    /// is filled with the new entities
+    pub fn spawn_batch<T: ComponentTuple>(
+        &mut self,
+        components: impl IntoIterator<Item = T>,
+    ) -> Vec<Entity> {
+        let mut components = components.into_iter().peekable();
+        let (additional, _) = components.size_hint();
+        self.reserve_entities(additional);
+
+        let tick = self.increment_change_tick();
+        let archetype = Archetype::with_components::<T>();
+        let archetype_index = self.archetypes_container.reserve_archetype(&archetype);
+        let offsets = unsafe {
+            let (archetype, layout) = self
+                .archetypes_container
+                .get_archetype_with_layout_unchecked(archetype_index);
+            T::get_offsets_unchecked(archetype, layout)
+        };
+
+        let hooked_type_ids = if self.lifecycle_hooks.has_component_hooks() {
+            T::type_ids()
+        } else {
+            Vec::new()
+        };
+
+        let mut entities = Vec::with_capacity(additional);
+        let mut page_entity_ids = Vec::new();
+        while components.peek().is_some() {
+            let page_index = self
+                .archetypes_container
+                .reserve_page_with_free_space(archetype_index);
+
+            // Entities are added after the components are written, so the panic
+            // of the iterator only leaks the written components
+            let written_count = unsafe {
+                let page = self
+                    .archetypes_container
+                    .get_page_by_index_unchecked(page_index as u32);
+                let first_index = page.entity_count();
+                let ptrs = T::get_ptrs_mut(page, &offsets);
+
+                let mut written_count = 0;
+                for entity_components in components
+                    .by_ref()
+                    .take(page.entity_capacity() - first_index)
+                {
+                    let entity_ptrs =
+                        T::add_to_ptrs_mut(&ptrs, first_index + written_count);
+                    T::write_to_ptrs(entity_ptrs, entity_components);
+                    written_count += 1;
+                }
+
+                written_count
+            };
+
+            page_entity_ids.clear();
+            for _ in 0..written_count {
+                let entity = self.register_new_entity();
+                page_entity_ids.push(entity.id);
+                entities.push(entity);
+            }
+
+            let first_index = self.archetypes_container.add_entities_to_page(
+                page_index,
+                &page_entity_ids,
+                tick,
+            );
+
+            for (i, &id) in page_entity_ids.iter().enumerate() {
+                unsafe {
+                    self.set_page_index_unchecked(id, page_index as u32);
+                    self.set_index_in_page_unchecked(id, (first_index + i) as u32);
+                    self.enable_archetype_unchecked(id);
+                }
+            }
+        }
+
+        if !hooked_type_ids.is_empty() {
+            for &entity in &entities {
+                self.call_on_add_hooks(entity, &hooked_type_ids, None);
+            }
+        }
+
+        entities
+    }
+
+    /// Creates the entity in the archetype and writes all of its components with
+    /// `write`. If `write` panics, the entity is removed without the drop glue,
+    /// so `write` must drop the components, that it has already written
+    pub(crate) fn create_entity_in_archetype_index(
+        &mut self,
+        archetype_index: usize,
+        tick: u32,
+        write: impl FnOnce(&ArchetypesContainer, EntityInArchetype),
+    ) -> Entity {
+        let entity = self.register_new_entity();
+        let entity_in_arch = self.archetypes_container.add_entity_to_archetype_index(
+            entity.id,
+            archetype_index,
+            tick,
+        );
 
         unsafe {
             self.set_page_index_unchecked(entity.id, entity_in_arch.page_index);
             self.set_index_in_page_unchecked(entity.id, entity_in_arch.index_in_page);
-            self.enable_archetype_unchecked(entity.id)
+            self.enable_archetype_unchecked(entity.id);
         }
 
+        let guard = UninitEntityGuard {
+            store: self,
+            entity,
+            entity_in_arch,
+        };
+        write(&guard.store.archetypes_container, entity_in_arch);
+        std::mem::forget(guard);
+
         entity
     }
 
+    pub(crate) fn reserve_entities(&mut self, additional: usize) {
+        let old_capacity = self.entities_capacity();
+        self.entities_container.reserve(additional);
+
+        if self.entities_capacity() != old_capacity {
+            self.grow_entities_internal(old_capacity);
+        }
+    }
+
     fn register_new_entity(&mut self) -> Entity {
         let creation = self.entities_container.create_entity();
         if creation.container_was_grow() {
@@ -82,7 +317,110 @@ impl Store {
         creation.entity
     }
 
+    /// Creates the entity with the clones of the entity components and its tags.
+    /// Components with drop glue must be registered with [`Store::register_clone`].
+    /// The clone has no parent, children and orders
+    pub fn clone_entity(&mut self, entity: Entity) -> Entity {
+        self.clone_entity_internal(entity, false)
+    }
+
+    /// Same as [`Store::clone_entity`], but the clone also follows the entity
+    /// in every order group of the entity
+    pub fn clone_entity_with_order_groups(&mut self, entity: Entity) -> Entity {
+        self.clone_entity_internal(entity, true)
+    }
+
+    fn clone_entity_internal(
+        &mut self,
+        entity: Entity,
+        with_order_groups: bool,
+    ) -> Entity {
+        assert!(self.is_alive(entity), "Can't clone the dead entity");
+
+        let source = if unsafe { self.has_archetype_unchecked(entity.id) } {
+            let entity_in_arch =
+                unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
+            let arch_index = self
+                .archetypes_container
+                .get_archetype_index_by_page(entity_in_arch.page_index as usize);
+            let clone_fns = self
+                .component_clone_fns
+                .resolve(&self.archetypes_container.get_archetypes()[arch_index]);
+
+            Some((arch_index, clone_fns))
+        } else {
+            None
+        };
+
+        let clone = match &source {
+            Some((arch_index, clone_fns)) => {
+                let tick = self.increment_change_tick();
+                let src = unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
+                self.create_entity_in_archetype_index(
+                    *arch_index,
+                    tick,
+                    |container, dst| {
+                        container.clone_entity_components(src, dst, clone_fns)
+                    },
+                )
+            }
+            None => self.create_entity(),
+        };
+
+        self.tag_container.copy_tags(entity.id, clone.id);
+        if with_order_groups {
+            self.order_group_container
+                .add_id_next_to_in_groups_of(clone.id, entity.id);
+        }
+
+        if source.is_some() && self.lifecycle_hooks.has_component_hooks() {
+            let (page_view, _) = unsafe { self.get_page_info(clone.id) };
+            let type_ids = page_view.archetype.included_ids().to_vec();
+            self.call_on_add_hooks(clone, &type_ids, None);
+        }
+
+        if self.lifecycle_hooks.has_tag_hooks() {
+            for type_id in self.tag_container.get_entity_tag_ids(clone.id) {
+                self.lifecycle_hooks.call_on_tag_added(type_id, clone);
+            }
+        }
+
+        clone
+    }
+
+    /// Destroys the entity with all of its descendants in the hierarchy. A dead entity
+    /// panics in debug and is ignored in release, its id may belong to another entity
     pub fn destroy_entity(&mut self, entity: Entity) {
+        let is_alive = self.is_alive(entity);
+        debug_assert!(is_alive, "Entity is already dead");
+        if !is_alive {
+            return;
+        }
+
+        if self
+            .hierarchy_container
+            .get_first_child_id(entity.id)
+            .is_some()
+        {
+            let descendant_ids =
+                self.hierarchy_container.collect_descendant_ids(entity.id);
+
+            // Children are destroyed before the parents, so every entity is detached
+            // from the alive parent
+            for &id in descendant_ids.iter().rev() {
+                let descendant = unsafe { self.get_entity_by_id_unchecked(id) };
+                self.destroy_entity_without_descendants(descendant);
+            }
+        }
+
+        self.destroy_entity_without_descendants(entity);
+    }
+
+    fn destroy_entity_without_descendants(&mut self, entity: Entity) {
+        if self.lifecycle_hooks.has_destroy_hooks() {
+            self.call_destroy_hooks(entity);
+        }
+
         let entity_id = entity.id;
         let has_archetype = unsafe { self.has_archetype_unchecked(entity.id) };
 
@@ -101,16 +439,22 @@ impl Store {
         self.tag_container.remove_all_tags_for_entity(entity_id);
         self.order_group_container
             .remove_all_orders_for_id(entity_id);
+        self.hierarchy_container.remove_parent(entity_id);
     }
 
-    pub fn add_components<T: ComponentTuple>(&mut self, entity: Entity, components: T) {
+    pub fn add_components<T: ComponentTuple + 'static>(
+        &mut self,
+        entity: Entity,
+        components: T,
+    ) {
         if self.is_alive(entity) == false {
             return;
         }
 
-        // TODO: Optimize, refactor. Avoid using archetype/builder allocations
+        let tick = self.increment_change_tick();
 
         let has_archetype = unsafe { self.has_archetype_unchecked(entity.id) };
+        let mut previous_arch_index = None;
 
         let entity_in_archetype = if has_archetype {
             let entity_in_arch =
@@ -126,19 +470,40 @@ impl Store {
             };
 
             if T::is_archetype_include_types(arch) {
-                entity_in_arch
-            } else {
-                let new_arch = ArchetypeBuilder::new()
-                    .include_archetype(&arch)
-                    .include_components::<T>()
-                    .build();
-
-                self.move_entity_to_other_archetype(entity, &new_arch)
+                unsafe {
+                    let page_view = self
+                        .archetypes_container
+                        .get_page_view_unchecked(entity_in_arch.page_index as usize);
+
+                    let refs = page_view.get_components_refs_mut_unchecked::<T>(
+                        entity_in_arch.index_in_page as usize,
+                    );
+                    T::assign_to_refs(refs, components);
+                    T::mark_changed_in_page(page_view.page, page_view.archetype, tick);
+                }
+
+                return;
+            }
+
+            let new_arch_index = self
+                .archetypes_container
+                .reserve_archetype_with_added::<T>(arch_index);
+            previous_arch_index = Some(arch_index);
+
+            // Old values of the added components are dropped before the move,
+            // because the new values will be written over them without drop
+            unsafe {
+                self.archetypes_container
+                    .get_page_view_unchecked(entity_in_arch.page_index as usize)
+                    .drop_components_in_place::<T>(entity_in_arch.index_in_page as usize);
             }
+
+            self.move_entity_to_other_archetype(entity, new_arch_index, tick)
         } else {
             let archetype = Archetype::with_components::<T>();
-            let entity_in_arch =
-                self.archetypes_container.add_entity(entity.id, &archetype);
+            let entity_in_arch = self
+                .archetypes_container
+                .add_entity(entity.id, &archetype, tick);
 
             unsafe {
                 self.set_page_index_unchecked(entity.id, entity_in_arch.page_index);
@@ -153,11 +518,16 @@ impl Store {
                 .archetypes_container
                 .get_page_view_unchecked(entity_in_archetype.page_index as usize);
 
-            let refs = page_view.get_components_refs_mut_unchecked::<T>(
+            page_view.write_components_unchecked(
                 entity_in_archetype.index_in_page as usize,
+                components,
             );
-            T::assign_to_refs(refs, components);
+            T::mark_changed_in_page(page_view.page, page_view.archetype, tick);
         };
+
+        if self.lifecycle_hooks.has_component_hooks() {
+            self.call_on_add_hooks(entity, &T::type_ids(), previous_arch_index);
+        }
     }
 
     pub fn remove_components<T: ComponentTuple + 'static>(&mut self, entity: Entity) {
@@ -171,23 +541,92 @@ impl Store {
             .archetypes_container
             .get_archetype_index_by_page(prev_entity_in_arch.page_index as usize);
 
-        let prev_arch = unsafe {
-            self.archetypes_container
-                .get_archetypes()
-                .get_unchecked(prev_arch_index)
-        };
-        let new_arch = ArchetypeBuilder::new()
-            .include_archetype(&prev_arch)
-            .exclude_components::<T>()
-            .build();
+        let new_arch_index = self
+            .archetypes_container
+            .reserve_archetype_with_removed::<T>(prev_arch_index);
+        if new_arch_index == prev_arch_index {
+            return;
+        }
 
-        self.move_entity_to_other_archetype(entity, &new_arch);
+        if self.lifecycle_hooks.has_component_hooks() {
+            self.call_on_remove_hooks(entity, &T::type_ids());
+        }
+
+        let tick = self.increment_change_tick();
+        self.move_entity_to_other_archetype(entity, new_arch_index, tick);
     }
 
-    fn move_entity_to_other_archetype(
+    /// Moves the alive entity to the archetype with the zero-sized tag component
+    pub(crate) fn add_archetype_tag(
         &mut self,
         entity: Entity,
-        new_arch: &Archetype,
+        component_type: &ComponentType,
+    ) {
+        let tick = self.increment_change_tick();
+        let has_archetype = unsafe { self.has_archetype_unchecked(entity.id) };
+        if !has_archetype {
+            let archetype = ArchetypeBuilder::new()
+                .include_component_types(&[*component_type])
+                .build();
+            self.attach_entity_to_archetype(entity.id, &archetype);
+
+            return;
+        }
+
+        let entity_in_arch = unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
+        let arch_index = self
+            .archetypes_container
+            .get_archetype_index_by_page(entity_in_arch.page_index as usize);
+        let new_arch_index = self
+            .archetypes_container
+            .reserve_archetype_with_added_type(arch_index, component_type);
+        if new_arch_index != arch_index {
+            self.move_entity_to_other_archetype(entity, new_arch_index, tick);
+        }
+    }
+
+    /// Moves the alive entity to the archetype without the zero-sized tag component.
+    /// The entity without other components is detached from the archetypes
+    pub(crate) fn remove_archetype_tag(
+        &mut self,
+        entity: Entity,
+        component_type: &ComponentType,
+    ) {
+        if !self.is_valid_entity_with_archetype(entity) {
+            return;
+        }
+
+        let entity_in_arch = unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
+        let arch_index = self
+            .archetypes_container
+            .get_archetype_index_by_page(entity_in_arch.page_index as usize);
+        let archetype = &self.archetypes_container.get_archetypes()[arch_index];
+        if archetype
+            .find_component_index(component_type.id())
+            .is_none()
+        {
+            return;
+        }
+
+        if archetype.component_count() == 1 {
+            self.swap_remove_internal(entity_in_arch);
+            unsafe { self.disable_archetype_unchecked(entity.id) };
+
+            return;
+        }
+
+        let new_arch_index = self
+            .archetypes_container
+            .reserve_archetype_with_removed_type(arch_index, component_type);
+        let tick = self.increment_change_tick();
+        self.move_entity_to_other_archetype(entity, new_arch_index, tick);
+    }
+
+    pub(crate) fn move_entity_to_other_archetype(
+        &mut self,
+        entity: Entity,
+        new_arch_index: usize,
+        tick: u32,
     ) -> EntityInArchetype {
         let prev_entity_in_arch =
             unsafe { self.get_entity_in_archetype_unchecked(entity.id) };
@@ -199,14 +638,18 @@ impl Store {
             entity.id,
             prev_entity_in_arch,
             prev_arch_index,
-            &new_arch,
+            new_arch_index,
+            tick,
         );
         unsafe {
             self.set_page_index_unchecked(entity.id, entity_in_arch.page_index);
             self.set_index_in_page_unchecked(entity.id, entity_in_arch.index_in_page);
         }
 
-        self.swap_remove_internal(prev_entity_in_arch);
+        let swap_remove = self
+            .archetypes_container
+            .swap_remove_moved_entity(prev_entity_in_arch);
+        self.update_swap_removed_entity(prev_entity_in_arch, swap_remove);
 
         entity_in_arch
     }
@@ -215,7 +658,14 @@ impl Store {
         let swap_remove = self
             .archetypes_container
             .swap_remove_entity(entity_in_archetype);
+        self.update_swap_removed_entity(entity_in_archetype, swap_remove);
+    }
 
+    fn update_swap_removed_entity(
+        &mut self,
+        entity_in_archetype: EntityInArchetype,
+        swap_remove: Option<SwapRemoveInfo>,
+    ) {
         if let Some(swap_remove) = swap_remove {
             unsafe {
                 self.set_index_in_page_unchecked(
@@ -250,14 +700,23 @@ impl Store {
         entity: Entity,
     ) -> Option<T::MutRefTuple<'a>> {
         if self.is_valid_entity_with_archetype(entity) {
+            let tick = self.increment_change_tick();
             let (page_view, index_in_page) = unsafe { self.get_page_info(entity.id) };
-            page_view.get_components_refs_mut::<T>(index_in_page)
+            let refs = page_view.get_components_refs_mut::<T>(index_in_page);
+            if refs.is_some() {
+                T::mark_changed_in_page(page_view.page, page_view.archetype, tick);
+            }
+
+            refs
         } else {
             None
         }
     }
 
-    unsafe fn get_page_info(&self, entity_id: u32) -> (ArchetypeDataPageView, usize) {
+    pub(crate) unsafe fn get_page_info(
+        &self,
+        entity_id: u32,
+    ) -> (ArchetypeDataPageView<'_>, usize) {
         (
             self.get_page_view_unchecked(entity_id),
             self.get_index_in_page_unchecked(entity_id) as usize,
@@ -309,6 +768,7 @@ impl Store {
 
         self.tag_container.grow(new_capacity);
         self.order_group_container.grow(new_capacity);
+        self.hierarchy_container.grow(new_capacity);
     }
 
     #[inline(always)]
@@ -371,6 +831,68 @@ impl Store {
     }
 }
 
+impl<'a> Drop for UninitEntityGuard<'a> {
+    fn drop(&mut self) {
+        let swap_remove = self
+            .store
+            .archetypes_container
+            .swap_remove_moved_entity(self.entity_in_arch);
+        self.store
+            .update_swap_removed_entity(self.entity_in_arch, swap_remove);
+
+        unsafe { self.store.disable_archetype_unchecked(self.entity.id) };
+        self.store.entities_container.destroy_entity(self.entity);
+    }
+}
+
+/// Clones the entities with their components, tags, orders and hierarchy. Lifecycle
+/// hooks, events and resources aren't cloned. Panics, if the component with drop
+/// glue isn't registered with [`Store::register_clone`]
+impl Clone for Store {
+    fn clone(&self) -> Self {
+        let capacity = self.entities_capacity();
+        let archetypes_container = self
+            .archetypes_container
+            .clone_with(&self.component_clone_fns);
+
+        let (entity_has_archetype_bit_vec, entity_to_index_in_page, entity_to_page) = unsafe {
+            let has_archetype_bit_vec = bitvec_utils::alloc(capacity);
+            let to_index_in_page = mem_utils::alloc(capacity);
+            let to_page = mem_utils::alloc(capacity);
+
+            self.entity_has_archetype_bit_vec.copy_to_nonoverlapping(
+                has_archetype_bit_vec,
+                bitvec_utils::get_len(capacity),
+            );
+            self.entity_to_index_in_page
+                .copy_to_nonoverlapping(to_index_in_page, capacity);
+            self.entity_to_page
+                .copy_to_nonoverlapping(to_page, capacity);
+
+            (has_archetype_bit_vec, to_index_in_page, to_page)
+        };
+
+        Store {
+            entities_container: self.entities_container.clone(),
+            archetypes_container,
+            tag_container: self.tag_container.clone(),
+            order_group_container: self.order_group_container.clone(),
+            hierarchy_container: self.hierarchy_container.clone(),
+            lifecycle_hooks: LifecycleHooks::default(),
+            events_container: EventsContainer::new(),
+            resources_container: ResourcesContainer::new(),
+            component_clone_fns: self.component_clone_fns.clone(),
+            reflection_registry: self.reflection_registry.clone(),
+
+            entity_has_archetype_bit_vec,
+            entity_to_index_in_page,
+            entity_to_page,
+
+            change_tick: AtomicU32::new(self.change_tick()),
+        }
+    }
+}
+
 impl Drop for Store {
     fn drop(&mut self) {
         unsafe {
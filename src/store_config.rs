@@ -0,0 +1,57 @@
+use crate::{
+    archetype_data_page::ArchetypeDataPage, component_tuple::ComponentTuple,
+    store::ENTITIES_DEFAULT_CAPACITY, Archetype,
+};
+
+/// Initial parameters of the [`crate::Store`]
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub(crate) entities_capacity: usize,
+    pub(crate) page_size: usize,
+    pub(crate) archetype_page_sizes: Vec<(Archetype, usize)>,
+}
+
+impl StoreConfig {
+    pub fn new() -> Self {
+        Self {
+            entities_capacity: ENTITIES_DEFAULT_CAPACITY,
+            page_size: ArchetypeDataPage::PAGE_SIZE_BYTES,
+            archetype_page_sizes: Vec::new(),
+        }
+    }
+
+    pub fn entities_capacity(mut self, capacity: usize) -> Self {
+        self.entities_capacity = capacity;
+        self
+    }
+
+    /// Size of the components data page in bytes for all archetypes without override
+    pub fn page_size(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "Page size must be positive");
+        self.page_size = bytes;
+        self
+    }
+
+    /// Overrides the data page size for the archetype with exactly `T` components
+    pub fn archetype_page_size<T: ComponentTuple>(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "Page size must be positive");
+
+        let archetype = Archetype::with_components::<T>();
+        match self
+            .archetype_page_sizes
+            .iter_mut()
+            .find(|(arch, _)| arch.is_same_as(&archetype))
+        {
+            Some((_, size)) => *size = bytes,
+            None => self.archetype_page_sizes.push((archetype, bytes)),
+        }
+
+        self
+    }
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
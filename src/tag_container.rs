@@ -8,6 +8,23 @@ pub(crate) struct TagContainer {
     entity_capacity: usize,
 }
 
+/// Entities mask of the query tag filters. Single tag filter reads the tag bit vector
+/// in place, the mask is allocated only to combine multiple filters
+pub(crate) enum TagFilterMask<'a> {
+    All,
+    WithTag(&'a [u32]),
+    WithoutTag(&'a [u32]),
+    Combined(Vec<u32>),
+}
+
+/// Iterator over the alive entities with the tag, which walks the tag bit vector
+pub struct TagQueryIter<'a> {
+    store: &'a Store,
+    bitvec_words: &'a [u32],
+    word_index: usize,
+    current_word: u32,
+}
+
 impl TagContainer {
     pub fn new(entity_capacity: usize) -> Self {
         Self {
@@ -17,21 +34,83 @@ impl TagContainer {
         }
     }
 
-    pub fn has_tag_bitvec<T: Tag + 'static>(&self) -> *const u32 {
-        let type_id = TypeId::of::<T>();
-        let tag_index = match self.tag_ids.binary_search(&type_id) {
-            Ok(i) => i,
-            Err(_) => panic!("Failed to find tag with id: {type_id:?}")
-        };
+    /// Bit vector of the entities with the tag. `None` if the tag was never added
+    pub fn get_has_tag_bitvec(&self, type_id: TypeId) -> Option<*const u32> {
+        match self.tag_ids.binary_search(&type_id) {
+            Ok(i) => unsafe { Some(*self.entity_id_to_has_tag_bitvecs.get_unchecked(i)) },
+            Err(_) => None,
+        }
+    }
 
-        unsafe {
-            *self.entity_id_to_has_tag_bitvecs.get_unchecked(tag_index)
+    /// Words of the tag bit vector, empty if the tag was never added
+    pub fn get_has_tag_words(&self, type_id: TypeId) -> &[u32] {
+        match self.get_has_tag_bitvec(type_id) {
+            Some(bitvec) => unsafe {
+                std::slice::from_raw_parts(
+                    bitvec,
+                    bitvec_utils::get_len(self.entity_capacity),
+                )
+            },
+            None => &[],
+        }
+    }
+
+    /// Mask of all entity ids, which is narrowed by the tag filters
+    pub fn new_entities_mask(&self) -> Vec<u32> {
+        vec![u32::MAX; bitvec_utils::get_len(self.entity_capacity)]
+    }
+
+    /// Keeps only entities with all of the tags in the mask
+    pub fn intersect_mask_with_all(&self, mask: &mut [u32], tag_ids: &[TypeId]) {
+        for &type_id in tag_ids {
+            match self.get_has_tag_bitvec(type_id) {
+                Some(bitvec) => Self::combine_mask_words(mask, bitvec, |m, b| m & b),
+                None => mask.fill(0),
+            }
+        }
+    }
+
+    /// Keeps only entities with at least one of the tags in the mask
+    pub fn intersect_mask_with_any(&self, mask: &mut [u32], tag_ids: &[TypeId]) {
+        let mut any_mask = vec![0; mask.len()];
+        for &type_id in tag_ids {
+            if let Some(bitvec) = self.get_has_tag_bitvec(type_id) {
+                Self::combine_mask_words(&mut any_mask, bitvec, |m, b| m | b);
+            }
+        }
+
+        for (word, any_word) in mask.iter_mut().zip(any_mask) {
+            *word &= any_word;
+        }
+    }
+
+    /// Removes entities with any of the tags from the mask
+    pub fn subtract_mask_tags(&self, mask: &mut [u32], tag_ids: &[TypeId]) {
+        for &type_id in tag_ids {
+            if let Some(bitvec) = self.get_has_tag_bitvec(type_id) {
+                Self::combine_mask_words(mask, bitvec, |m, b| m & !b);
+            }
+        }
+    }
+
+    /// The mask must be created by [`Self::new_entities_mask`]
+    #[inline]
+    fn combine_mask_words(
+        mask: &mut [u32],
+        bitvec: *const u32,
+        f: impl Fn(u32, u32) -> u32,
+    ) {
+        let bitvec = unsafe { std::slice::from_raw_parts(bitvec, mask.len()) };
+        for (word, &bits) in mask.iter_mut().zip(bitvec) {
+            *word = f(*word, bits);
         }
     }
 
     pub fn add_tag<T: Tag + 'static>(&mut self, entity_id: u32) {
-        let type_id = TypeId::of::<T>();
+        self.add_tag_by_id(TypeId::of::<T>(), entity_id);
+    }
 
+    pub fn add_tag_by_id(&mut self, type_id: TypeId, entity_id: u32) {
         let tag_index = match self.tag_ids.binary_search(&type_id) {
             Ok(i) => i,
             Err(i) => {
@@ -73,6 +152,37 @@ impl TagContainer {
         }
     }
 
+    pub fn has_tag_by_id(&self, type_id: TypeId, entity_id: u32) -> bool {
+        match self.get_has_tag_bitvec(type_id) {
+            Some(bitvec) => unsafe {
+                bitvec_utils::is_bit_on(bitvec, entity_id as usize)
+            },
+            None => false,
+        }
+    }
+
+    /// Adds all tags of the `src_id` entity to the `dst_id` entity
+    pub fn copy_tags(&mut self, src_id: u32, dst_id: u32) {
+        for &mut bitvec in &mut self.entity_id_to_has_tag_bitvecs {
+            unsafe {
+                if bitvec_utils::is_bit_on(bitvec, src_id as usize) {
+                    bitvec_utils::set_bit_on(bitvec, dst_id as usize);
+                }
+            }
+        }
+    }
+
+    pub fn get_entity_tag_ids(&self, entity_id: u32) -> Vec<TypeId> {
+        self.tag_ids
+            .iter()
+            .zip(&self.entity_id_to_has_tag_bitvecs)
+            .filter(|(_, &bitvec)| unsafe {
+                bitvec_utils::is_bit_on(bitvec, entity_id as usize)
+            })
+            .map(|(&type_id, _)| type_id)
+            .collect()
+    }
+
     pub fn remove_all_tags_for_entity(&mut self, entity_id: u32) {
         for &mut bitvec in &mut self.entity_id_to_has_tag_bitvecs {
             unsafe {
@@ -93,21 +203,161 @@ impl TagContainer {
     }
 }
 
+impl<'a> TagFilterMask<'a> {
+    #[inline]
+    pub fn contains(&self, entity_id: u32) -> bool {
+        match self {
+            Self::All => true,
+            Self::WithTag(words) => {
+                bitvec_utils::is_bit_on_in_words(words, entity_id as usize)
+            }
+            Self::WithoutTag(words) => {
+                !bitvec_utils::is_bit_on_in_words(words, entity_id as usize)
+            }
+            Self::Combined(mask) => unsafe {
+                bitvec_utils::is_bit_on(mask.as_ptr(), entity_id as usize)
+            },
+        }
+    }
+
+    /// Mask of all entity ids, which can be combined with the next filters
+    pub fn into_combined(self, tag_container: &TagContainer) -> Vec<u32> {
+        let mut mask = match self {
+            Self::Combined(mask) => return mask,
+            _ => tag_container.new_entities_mask(),
+        };
+
+        match self {
+            Self::WithTag([]) => mask.fill(0),
+            Self::WithTag(words) => {
+                for (word, &bits) in mask.iter_mut().zip(words) {
+                    *word &= bits;
+                }
+            }
+            Self::WithoutTag(words) => {
+                for (word, &bits) in mask.iter_mut().zip(words) {
+                    *word &= !bits;
+                }
+            }
+            Self::All | Self::Combined(_) => {}
+        }
+
+        mask
+    }
+}
+
 impl Store {
     #[inline(always)]
     pub fn add_tag<T: Tag + 'static>(&mut self, entity: Entity) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let had_tag = self.tag_container.has_tag::<T>(entity.id);
         self.tag_container.add_tag::<T>(entity.id);
+        if had_tag {
+            return;
+        }
+
+        if let Some(component_type) = T::archetype_component() {
+            self.add_archetype_tag(entity, &component_type);
+        }
+
+        if self.lifecycle_hooks.has_tag_hooks() {
+            self.lifecycle_hooks
+                .call_on_tag_added(TypeId::of::<T>(), entity);
+        }
     }
 
     #[inline(always)]
     pub fn remove_tag<T: Tag + 'static>(&mut self, entity: Entity) {
+        if !self.is_alive(entity) {
+            return;
+        }
+
+        let had_tag = self.tag_container.has_tag::<T>(entity.id);
         self.tag_container.remove_tag::<T>(entity.id);
+        if !had_tag {
+            return;
+        }
+
+        if let Some(component_type) = T::archetype_component() {
+            self.remove_archetype_tag(entity, &component_type);
+        }
+
+        if self.lifecycle_hooks.has_tag_hooks() {
+            self.lifecycle_hooks
+                .call_on_tag_removed(TypeId::of::<T>(), entity);
+        }
     }
 
     #[inline(always)]
     pub fn has_tag<T: Tag + 'static>(&self, entity: Entity) -> bool {
         self.tag_container.has_tag::<T>(entity.id)
     }
+
+    /// Iterates the entities with the tag, including entities without components
+    pub fn tag_query_iter<T: Tag>(&self) -> TagQueryIter<'_> {
+        let bitvec_words = self.tag_container.get_has_tag_words(TypeId::of::<T>());
+
+        TagQueryIter {
+            store: self,
+            bitvec_words,
+            word_index: 0,
+            current_word: bitvec_words.first().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn count_tagged<T: Tag>(&self) -> usize {
+        self.tag_container
+            .get_has_tag_words(TypeId::of::<T>())
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+impl<'a> Iterator for TagQueryIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.current_word == 0 {
+                self.word_index += 1;
+                self.current_word = *self.bitvec_words.get(self.word_index)?;
+            }
+
+            let bit = self.current_word.trailing_zeros();
+            self.current_word &= self.current_word - 1;
+
+            // Tags are removed on destroy, dead ids are skipped for safety anyway
+            let id = self.word_index as u32 * u32::BITS + bit;
+            if self.store.entities_container.is_alive_at_index(id as usize) {
+                return Some(unsafe { self.store.get_entity_by_id_unchecked(id) });
+            }
+        }
+    }
+}
+
+impl Clone for TagContainer {
+    fn clone(&self) -> Self {
+        let bitvec_len = bitvec_utils::get_len(self.entity_capacity);
+        let entity_id_to_has_tag_bitvecs = self
+            .entity_id_to_has_tag_bitvecs
+            .iter()
+            .map(|&bitvec| unsafe {
+                let cloned = bitvec_utils::alloc(self.entity_capacity);
+                bitvec.copy_to_nonoverlapping(cloned, bitvec_len);
+                cloned
+            })
+            .collect();
+
+        Self {
+            tag_ids: self.tag_ids.clone(),
+            entity_id_to_has_tag_bitvecs,
+            entity_capacity: self.entity_capacity,
+        }
+    }
 }
 
 impl Drop for TagContainer {
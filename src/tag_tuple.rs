@@ -0,0 +1,32 @@
+use std::any::TypeId;
+
+use crate::{type_ids, Tag};
+
+/// Tag or tuple of tags, used by the multiple tags filters of the queries
+pub trait TagTuple {
+    fn type_ids() -> Vec<TypeId>;
+}
+
+macro_rules! tags_tuple_impl {
+    ($($T:ident),*) => {
+        #[allow(unused_parens)]
+        impl<$($T: Tag),*> TagTuple for ($($T),*) {
+            fn type_ids() -> Vec<TypeId> {
+                type_ids!($($T),*).to_vec()
+            }
+        }
+    };
+}
+
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6, T7);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5, T6);
+tags_tuple_impl!(T0, T1, T2, T3, T4, T5);
+tags_tuple_impl!(T0, T1, T2, T3, T4);
+tags_tuple_impl!(T0, T1, T2, T3);
+tags_tuple_impl!(T0, T1, T2);
+tags_tuple_impl!(T0, T1);
+tags_tuple_impl!(T0);
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::{archetype::ArchetypesUnion, Archetype, ArchetypeBuilder, Component};
+    use crate::{
+        archetype::ArchetypesUnion, archetypes_container::ArchetypesContainer, Archetype,
+        ArchetypeBuilder, Component, Store, StoreConfig,
+    };
 
     #[derive(Component, Clone, Copy)]
     struct TestType1 {
@@ -135,6 +138,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn archetype_transitions_resolve_to_same_archetypes() {
+        let mut container = ArchetypesContainer::new(&StoreConfig::new());
+        let arch1 =
+            container.reserve_archetype(&Archetype::with_components::<TestType1>());
+        let arch12 = container
+            .reserve_archetype(&Archetype::with_components::<(TestType1, TestType2)>());
+
+        for _ in 0..2 {
+            assert_eq!(
+                container.reserve_archetype_with_added::<TestType2>(arch1),
+                arch12
+            );
+            assert_eq!(
+                container.reserve_archetype_with_removed::<TestType2>(arch12),
+                arch1
+            );
+            assert_eq!(
+                container.reserve_archetype_with_removed::<TestType3>(arch1),
+                arch1
+            );
+        }
+
+        let arch123 =
+            container.reserve_archetype_with_added::<(TestType2, TestType3)>(arch1);
+        assert!(container.get_archetypes()[arch123].is_same_as(
+            &Archetype::with_components::<(TestType1, TestType2, TestType3)>()
+        ));
+    }
+
+    #[test]
+    fn archetype_transitions_are_valid_after_archetype_slot_reuse() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, TestType1 { _v: 1.0 });
+        store.add_components(e, TestType2 { _v: 2.0 });
+        store.remove_components::<TestType1>(e);
+
+        // The slot of the freed (TestType1) archetype is taken by the other archetype
+        let other = store.create_entity();
+        store.add_components(other, TestType3 { _v: 3.0 });
+
+        store.add_components(e, TestType1 { _v: 4.0 });
+        store.remove_components::<TestType2>(e);
+
+        assert!(store.get_components_refs::<TestType2>(e).is_none());
+        assert_eq!(store.get_components_refs::<TestType1>(e).unwrap()._v, 4.0);
+        assert_eq!(
+            store.get_components_refs::<TestType3>(other).unwrap()._v,
+            3.0
+        );
+        assert!(store.get_components_refs::<TestType1>(other).is_none());
+    }
+
     fn assert_has_type_index<T: Component + 'static>(
         archetype: &Archetype,
         indices: &[usize],
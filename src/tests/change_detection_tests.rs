@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::{query, tick_utils, Component, Store};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Position {
+        x: f32,
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Velocity {
+        x: f32,
+    }
+
+    #[test]
+    fn changed_filter_skips_pages_without_writes() {
+        let mut store = Store::new();
+        for i in 0..10 {
+            let e = store.create_entity();
+            store.add_components(e, Position { x: i as f32 });
+        }
+
+        let mut changed = query::component::readonly::<Position>().changed::<Position>();
+        assert_eq!(store.component_query_iter(&mut changed).len(), 10);
+        assert_eq!(store.component_query_iter(&mut changed).len(), 0);
+
+        let mut read = query::component::readonly::<Position>();
+        store.component_query_iter(&mut read).for_each(|_| {});
+        assert_eq!(store.component_query_iter(&mut changed).len(), 0);
+
+        let mut write = query::component::write::<Position>();
+        store.component_query_iter(&mut write).for_each(|p| p.x += 1.);
+        assert_eq!(store.component_query_iter(&mut changed).len(), 10);
+        assert_eq!(store.component_query_iter(&mut changed).len(), 0);
+    }
+
+    #[test]
+    fn changed_filter_detects_writes_by_entity() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, (Position { x: 0. }, Velocity { x: 1. }));
+
+        let mut changed = query::component::readonly::<Position>().changed::<Velocity>();
+        assert_eq!(store.component_query_iter(&mut changed).len(), 1);
+
+        store.get_components_refs_mut::<Position>(e);
+        assert_eq!(store.component_query_iter(&mut changed).len(), 0);
+
+        store.get_components_refs_mut::<Velocity>(e).unwrap().x = 2.;
+        assert_eq!(store.component_query_iter(&mut changed).len(), 1);
+
+        store.add_components(e, Velocity { x: 3. });
+        assert_eq!(store.component_query_iter(&mut changed).len(), 1);
+        assert_eq!(store.component_query_iter(&mut changed).len(), 0);
+    }
+
+    #[test]
+    fn changed_filter_added_after_run_rematches_archetypes() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 0. });
+        let e = store.create_entity();
+        store.add_components(e, (Position { x: 0. }, Velocity { x: 1. }));
+
+        let mut query = query::component::readonly::<Position>();
+        assert_eq!(store.component_query_iter(&mut query).len(), 2);
+
+        store.get_components_refs_mut::<Velocity>(e).unwrap().x = 2.;
+        let mut query = query.changed::<Velocity>();
+        assert_eq!(store.component_query_iter(&mut query).len(), 1);
+
+        let mut query = query.added::<Velocity>();
+        store.add_components(e, Velocity { x: 3. });
+        assert_eq!(store.component_query_iter(&mut query).len(), 0);
+    }
+
+    #[test]
+    fn query_does_not_detect_own_writes() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 0. });
+
+        let mut query = query::component::write::<Position>().changed::<Position>();
+        store.component_query_iter(&mut query).for_each(|p| p.x += 1.);
+
+        assert_eq!(store.component_query_iter(&mut query).len(), 0);
+    }
+
+    #[test]
+    fn added_filter_returns_only_new_entities() {
+        let mut store = Store::new();
+        let mut entities = Vec::new();
+        for i in 0..10 {
+            let e = store.create_entity();
+            store.add_components(e, Position { x: i as f32 });
+            entities.push(e);
+        }
+
+        let mut added = query::component::readonly::<Position>().added::<Velocity>();
+        assert_eq!(store.component_query_iter(&mut added).len(), 0);
+
+        store.add_components(entities[3], Velocity { x: 3. });
+        store.add_components(entities[7], Velocity { x: 7. });
+
+        let mut values: Vec<_> = store
+            .component_query_iter(&mut added)
+            .map(|p| p.x)
+            .collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![3., 7.]);
+
+        let e = store.create_entity();
+        store.add_components(e, (Position { x: 10. }, Velocity { x: 10. }));
+        store.add_components(entities[7], Velocity { x: 8. });
+
+        let created: Vec<_> = store
+            .component_query_iter(&mut added)
+            .with_entities()
+            .map(|(e, p)| (e, p.x))
+            .collect();
+        assert_eq!(created, vec![(e, 10.)]);
+    }
+
+    #[test]
+    fn added_filter_keeps_tick_on_archetype_change() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 0. });
+
+        let mut added = query::component::readonly::<Position>().added::<Position>();
+        assert_eq!(store.component_query_iter(&mut added).len(), 1);
+
+        store.add_components(e, Velocity { x: 0. });
+        assert_eq!(store.component_query_iter(&mut added).len(), 0);
+
+        store.remove_components::<Velocity>(e);
+        assert_eq!(store.component_query_iter(&mut added).len(), 0);
+    }
+
+    #[test]
+    fn changed_filter_against_other_store_matches_all() {
+        let mut first = Store::new();
+        let mut second = Store::new();
+        for i in 0..3 {
+            let e = first.create_entity();
+            first.add_components(e, Position { x: i as f32 });
+        }
+        for i in 0..5 {
+            let e = second.create_entity();
+            second.add_components(e, Position { x: i as f32 });
+        }
+
+        let mut changed = query::component::readonly::<Position>().changed::<Position>();
+        let mut write = query::component::write::<Position>();
+        for _ in 0..10 {
+            first.component_query_iter(&mut write).for_each(|p| p.x += 1.);
+            assert_eq!(first.component_query_iter(&mut changed).len(), 3);
+        }
+
+        assert_eq!(second.component_query_iter(&mut changed).len(), 5);
+        assert_eq!(second.component_query_iter(&mut changed).len(), 0);
+    }
+
+    #[test]
+    fn ticks_are_compared_with_wrapping() {
+        assert!(tick_utils::is_newer(2, u32::MAX - 1, 5));
+        assert!(!tick_utils::is_newer(u32::MAX, 1, 5));
+        assert!(tick_utils::is_newer(u32::MAX, u32::MAX - 1, u32::MAX));
+        assert_eq!(tick_utils::latest(u32::MAX, 3, 4), 3);
+        assert_eq!(tick_utils::latest(u32::MAX, 3, u32::MAX), u32::MAX);
+    }
+}
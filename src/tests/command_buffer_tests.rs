@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use crate::{query, CommandBuffer, Component, Store, Tag};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Damage {
+        value: i32,
+    }
+
+    #[derive(Tag)]
+    struct DeadTag;
+
+    #[test]
+    fn apply_commands_creates_reserved_entities_with_components() {
+        let mut store = Store::new();
+        let mut commands = CommandBuffer::new();
+
+        let e = commands.create_entity();
+        commands.add_components(e, (Health { value: 10 }, Damage { value: 2 }));
+        commands.add_tag::<DeadTag>(e);
+
+        store.apply_commands(&mut commands);
+
+        let mut query = query::component::readonly::<(Health, Damage)>();
+        let created: Vec<_> = store
+            .component_query_iter(&mut query)
+            .with_entities()
+            .map(|(e, (h, d))| (e, *h, *d))
+            .collect();
+
+        assert_eq!(created.len(), 1);
+        let (created_entity, health, damage) = created[0];
+        assert_eq!(health, Health { value: 10 });
+        assert_eq!(damage, Damage { value: 2 });
+        assert!(store.has_tag::<DeadTag>(created_entity));
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    fn apply_commands_records_changes_during_query_iteration() {
+        let mut store = Store::new();
+        let mut commands = CommandBuffer::new();
+
+        for i in 0..10 {
+            let e = store.create_entity();
+            store.add_components(e, Health { value: i });
+        }
+
+        let mut query = query::component::readonly::<Health>();
+        for (e, health) in store.component_query_iter(&mut query).with_entities() {
+            if health.value % 2 == 0 {
+                commands.destroy_entity(e);
+            } else {
+                commands.add_components(
+                    e,
+                    Damage {
+                        value: health.value,
+                    },
+                );
+                commands.remove_components::<Health>(e);
+            }
+        }
+
+        store.apply_commands(&mut commands);
+
+        let mut query = query::component::readonly::<Health>();
+        assert_eq!(store.component_query_iter(&mut query).len(), 0);
+
+        let mut query = query::component::readonly::<Damage>();
+        let mut values: Vec<_> = store
+            .component_query_iter(&mut query)
+            .map(|d| d.value)
+            .collect();
+        values.sort();
+
+        assert_eq!(values, vec![1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn apply_commands_skips_dead_entities() {
+        let mut store = Store::new();
+        let mut commands = CommandBuffer::new();
+
+        let e = store.create_entity();
+        commands.destroy_entity(e);
+        commands.add_components(e, Health { value: 1 });
+        commands.destroy_entity(e);
+
+        store.apply_commands(&mut commands);
+
+        assert!(!store.is_alive(e));
+    }
+
+    #[test]
+    fn reserved_entities_are_resolved_per_apply() {
+        let mut store = Store::new();
+        let mut commands = CommandBuffer::new();
+
+        let first = commands.create_entity();
+        commands.add_components(first, Health { value: 1 });
+        store.apply_commands(&mut commands);
+
+        let second = commands.create_entity();
+        commands.add_components(second, Health { value: 2 });
+        store.apply_commands(&mut commands);
+
+        let mut query = query::component::readonly::<Health>();
+        let mut values: Vec<_> = store
+            .component_query_iter(&mut query)
+            .map(|h| h.value)
+            .collect();
+        values.sort();
+
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    fn apply_commands_sends_events_in_order() {
+        let mut store = Store::new();
+        let mut commands = CommandBuffer::new();
+
+        commands.send_event(Damage { value: 1 });
+        commands.send_event(Damage { value: 2 });
+        assert!(store.events::<Damage>().is_none());
+
+        store.apply_commands(&mut commands);
+
+        let events = store.events::<Damage>().unwrap();
+        let mut reader = events.reader();
+        let values: Vec<_> = events.read(&mut reader).map(|d| d.value).collect();
+        assert_eq!(values, vec![1, 2]);
+        assert!(commands.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't reserved by this command buffer")]
+    fn entity_reserved_by_other_buffer_panics() {
+        let mut commands = CommandBuffer::new();
+        let mut other_commands = CommandBuffer::new();
+
+        let e = other_commands.create_entity();
+        commands.add_components(e, Health { value: 1 });
+    }
+}
@@ -4,6 +4,10 @@ mod test {
         query::component::ComponentReadOnlyQuery, Archetype, Component,
         ComponentWriteQuery, Store,
     };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    };
 
     #[derive(Debug, PartialEq, Component, Clone, Copy)]
     struct Position {
@@ -32,7 +36,7 @@ mod test {
         let mut entities = Vec::new();
 
         for (_, _, arch) in &expected_data {
-            store.create_entity_with_archetype(arch);
+            unsafe { store.create_entity_with_archetype(arch) };
         }
 
         let mut query = ComponentWriteQuery::<(Position, Rotation)>::new();
@@ -51,8 +55,8 @@ mod test {
 
         let other_arch = Archetype::with_components::<(f32, u64)>();
 
-        store.create_entity_with_archetype(&other_arch);
-        store.create_entity_with_archetype(&other_arch);
+        unsafe { store.create_entity_with_archetype(&other_arch) };
+        unsafe { store.create_entity_with_archetype(&other_arch) };
 
         let mut query = ComponentReadOnlyQuery::<(Position, Rotation)>::new();
         let mut iter_count = 0;
@@ -83,7 +87,7 @@ mod test {
         let mut expected_to_iterate = Vec::new();
 
         for i in 0..INIT_ENTITIES_COUNT {
-            let e = store.create_entity_with_archetype(&arch);
+            let e = unsafe { store.create_entity_with_archetype(&arch) };
             let v = store.get_components_refs_mut::<usize>(e).unwrap();
             *v = i;
 
@@ -111,4 +115,168 @@ mod test {
             .iter()
             .all(|e| iterated_entities.contains(e)));
     }
+
+    #[test]
+    fn par_for_each_visits_every_entity_once() {
+        const ENTITIES_COUNT: usize = 10_000;
+        let mut store = Store::new();
+        let arch = Archetype::with_components::<(Position, usize)>();
+
+        for i in 0..ENTITIES_COUNT {
+            let e = unsafe { store.create_entity_with_archetype(&arch) };
+            let (pos, v) = store
+                .get_components_refs_mut::<(Position, usize)>(e)
+                .unwrap();
+            *pos = Position { x: 0., y: 0. };
+            *v = i;
+        }
+
+        let visited = AtomicUsize::new(0);
+        let mut query = ComponentWriteQuery::<(Position, usize)>::new();
+        store
+            .component_query_iter(&mut query)
+            .par_for_each_with_threads(4, |(pos, v)| {
+                pos.x = *v as f32;
+                pos.y += 1.;
+                visited.fetch_add(1, Ordering::Relaxed);
+            });
+
+        assert_eq!(visited.load(Ordering::Relaxed), ENTITIES_COUNT);
+
+        let mut query = ComponentReadOnlyQuery::<(Position, usize)>::new();
+        for (pos, v) in store.component_query_iter(&mut query) {
+            assert_eq!(
+                *pos,
+                Position {
+                    x: *v as f32,
+                    y: 1.
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn par_for_each_with_entities_returns_matching_entities() {
+        const ENTITIES_COUNT: usize = 5_000;
+        let mut store = Store::new();
+        let arch = Archetype::with_components::<usize>();
+
+        let mut entities = Vec::new();
+        for i in 0..ENTITIES_COUNT {
+            let e = unsafe { store.create_entity_with_archetype(&arch) };
+            *store.get_components_refs_mut::<usize>(e).unwrap() = i;
+            entities.push(e);
+        }
+
+        let visited = Mutex::new(Vec::new());
+        let mut query = ComponentReadOnlyQuery::<usize>::new();
+        store
+            .component_query_iter(&mut query)
+            .with_entities()
+            .par_for_each_with_threads(3, |e, v| {
+                assert_eq!(entities[*v], e);
+                visited.lock().unwrap().push(e);
+            });
+
+        assert_eq!(visited.into_inner().unwrap().len(), ENTITIES_COUNT);
+    }
+
+    #[test]
+    fn with_and_without_filters_match_archetypes() {
+        let mut store = Store::new();
+        let moving = store.create_entity();
+        store.add_components(moving, (Position { x: 1., y: 0. }, Rotation { angle: 0. }));
+        let fixed = store.create_entity();
+        store.add_components(fixed, Position { x: 2., y: 0. });
+
+        let mut with_query = ComponentReadOnlyQuery::<Position>::new().with::<Rotation>();
+        let with_entities: Vec<_> = store
+            .component_query_iter(&mut with_query)
+            .with_entities()
+            .map(|(e, p)| (e, p.x))
+            .collect();
+        assert_eq!(with_entities, vec![(moving, 1.)]);
+
+        let mut without_query =
+            ComponentReadOnlyQuery::<Position>::new().without::<Rotation>();
+        let without_entities: Vec<_> = store
+            .component_query_iter(&mut without_query)
+            .with_entities()
+            .map(|(e, p)| (e, p.x))
+            .collect();
+        assert_eq!(without_entities, vec![(fixed, 2.)]);
+    }
+
+    #[test]
+    fn optional_components_are_none_when_archetype_lacks_them() {
+        let mut store = Store::new();
+        let with_rotation = store.create_entity();
+        store.add_components(
+            with_rotation,
+            (Position { x: 1., y: 0. }, Rotation { angle: 90. }),
+        );
+        let without_rotation = store.create_entity();
+        store.add_components(without_rotation, Position { x: 2., y: 0. });
+
+        let mut write_query =
+            ComponentReadOnlyQuery::<Position>::new().optional_mut::<Rotation>();
+        for (_, rotation) in store.component_query_iter(&mut write_query) {
+            if let Some(rotation) = rotation {
+                rotation.angle += 90.;
+            }
+        }
+
+        let mut read_query = ComponentReadOnlyQuery::<Position>::new().optional::<Rotation>();
+        let mut result: Vec<_> = store
+            .component_query_iter(&mut read_query)
+            .map(|(p, r)| (p.x, r.copied()))
+            .collect();
+        result.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        assert_eq!(
+            result,
+            vec![(1., Some(Rotation { angle: 180. })), (2., None)]
+        );
+    }
+
+    #[test]
+    fn cached_query_sees_new_and_reused_archetypes() {
+        let mut store = Store::new();
+        let positioned = store.create_entity();
+        store.add_components(positioned, Position { x: 1., y: 0. });
+        let rotated = store.create_entity();
+        store.add_components(rotated, Rotation { angle: 90. });
+
+        let mut query = ComponentReadOnlyQuery::<Rotation>::new();
+        assert_eq!(store.component_query_iter(&mut query).len(), 1);
+
+        store.destroy_entity(rotated);
+        let reused = store.create_entity();
+        store.add_components(reused, 5usize);
+        assert_eq!(store.component_query_iter(&mut query).len(), 0);
+
+        let both = store.create_entity();
+        store.add_components(both, (Position { x: 2., y: 0. }, Rotation { angle: 45. }));
+        let angles: Vec<_> = store
+            .component_query_iter(&mut query)
+            .map(|r| r.angle)
+            .collect();
+        assert_eq!(angles, vec![45.]);
+    }
+
+    #[test]
+    fn cached_query_can_be_used_with_other_store() {
+        let mut first = Store::new();
+        let e = first.create_entity();
+        first.add_components(e, (Position { x: 1., y: 0. }, Rotation { angle: 0. }));
+
+        let mut second = Store::new();
+        let e = second.create_entity();
+        second.add_components(e, Position { x: 2., y: 0. });
+
+        let mut query = ComponentReadOnlyQuery::<Rotation>::new();
+        assert_eq!(first.component_query_iter(&mut query).len(), 1);
+        assert_eq!(second.component_query_iter(&mut query).len(), 0);
+        assert_eq!(first.component_query_iter(&mut query).len(), 1);
+    }
 }
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::{type_ids, Archetype, Component, Store, ClonedExtension};
-    use std::mem::MaybeUninit;
+    use crate::{component_ids, query, Archetype, ClonedExtension, Component, Store};
+    use std::{mem::MaybeUninit, rc::Rc};
 
     impl Component for f32 {}
     impl Component for u64 {}
@@ -17,6 +17,18 @@ mod tests {
         value: f64,
     }
 
+    #[derive(Component, Clone, Debug)]
+    struct DropComponent {
+        counter: Rc<()>,
+    }
+
+    #[derive(Component, Clone, Debug)]
+    struct NameComponent {
+        name: String,
+    }
+
+    impl Component for Rc<()> {}
+
     impl TestComponent1 {
         pub fn new(i: usize) -> TestComponent1 {
             TestComponent1 { value: i as i32 }
@@ -46,7 +58,7 @@ mod tests {
         let mut entities = Vec::new();
 
         for i in 0..entities_capacity {
-            let entity = store.create_entity_with_archetype(&archetype);
+            let entity = unsafe { store.create_entity_with_archetype(&archetype) };
             entities.push(entity);
 
             let (u64_v, u128_v) = store
@@ -69,7 +81,7 @@ mod tests {
         let mut store = Store::new();
         let archetype = Archetype::with_components::<(u64, u128)>();
 
-        let entity = store.create_entity_with_archetype(&archetype);
+        let entity = unsafe { store.create_entity_with_archetype(&archetype) };
         assert!(store.get_components_refs_mut::<f32>(entity).is_none());
     }
 
@@ -78,7 +90,7 @@ mod tests {
         let mut registry = Store::new();
         let archetype = Archetype::with_components::<u64>();
 
-        let entity = registry.create_entity_with_archetype(&archetype);
+        let entity = unsafe { registry.create_entity_with_archetype(&archetype) };
         registry.destroy_entity(entity);
 
         assert!(registry.get_components_refs_mut::<u64>(entity).is_none());
@@ -92,7 +104,7 @@ mod tests {
         let mut entity_to_swap_remove = MaybeUninit::uninit();
         let mut swapped_entity = MaybeUninit::uninit();
         for i in 0..4 {
-            let e = registry.create_entity_with_archetype(&archetype);
+            let e = unsafe { registry.create_entity_with_archetype(&archetype) };
             let (c1, c2) = registry
                 .get_components_refs_mut::<(TestComponent1, TestComponent2)>(e)
                 .unwrap();
@@ -136,7 +148,7 @@ mod tests {
 
         let arch = store.get_entity_archetype(e).unwrap();
 
-        assert!(arch.is_include_only_ids(&type_ids!(TestComponent1, TestComponent2)));
+        assert!(arch.is_include_only_ids(&component_ids!(TestComponent1, TestComponent2)));
     }
 
     #[test]
@@ -200,12 +212,12 @@ mod tests {
         assert!(store
             .get_entity_archetype(e1)
             .unwrap()
-            .is_include_only_ids(&type_ids!(TestComponent1)),);
+            .is_include_only_ids(&component_ids!(TestComponent1)),);
 
         assert!(store
             .get_entity_archetype(e2)
             .unwrap()
-            .is_include_only_ids(&type_ids!(TestComponent2)));
+            .is_include_only_ids(&component_ids!(TestComponent2)));
 
         assert_eq!(*actual1, expected1);
         assert_eq!(*actual2, expected2);
@@ -224,8 +236,8 @@ mod tests {
 
         let arch = store.get_entity_archetype(entity).unwrap();
 
-        assert!(arch.is_exclude_ids(&type_ids!(TestComponent2)));
-        assert!(arch.is_include_ids(&type_ids!(TestComponent1)));
+        assert!(arch.is_exclude_ids(&component_ids!(TestComponent2)));
+        assert!(arch.is_include_ids(&component_ids!(TestComponent1)));
     }
 
     #[test]
@@ -241,4 +253,231 @@ mod tests {
 
         assert_eq!(*comp2, expected_comp);
     }
+
+    #[test]
+    fn destroy_entity_drops_components() {
+        let counter = Rc::new(());
+        let mut store = Store::new();
+
+        let entities: Vec<_> = (0..3)
+            .map(|_| {
+                let e = store.create_entity();
+                store.add_components(
+                    e,
+                    DropComponent {
+                        counter: counter.clone(),
+                    },
+                );
+                e
+            })
+            .collect();
+
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        store.destroy_entity(entities[0]);
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        let remaining = store
+            .get_components_refs::<DropComponent>(entities[2])
+            .unwrap();
+        assert!(Rc::ptr_eq(&remaining.counter, &counter));
+    }
+
+    #[test]
+    fn remove_components_drops_only_removed_components() {
+        let removed = Rc::new(());
+        let kept = Rc::new(());
+        let mut store = Store::new();
+        let e = store.create_entity();
+
+        store.add_components(
+            e,
+            (
+                DropComponent {
+                    counter: removed.clone(),
+                },
+                kept.clone(),
+            ),
+        );
+        store.remove_components::<DropComponent>(e);
+
+        assert_eq!(Rc::strong_count(&removed), 1);
+        assert_eq!(Rc::strong_count(&kept), 2);
+    }
+
+    #[test]
+    fn add_components_moves_non_copy_components_between_archetypes() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+
+        store.add_components(
+            e,
+            NameComponent {
+                name: String::from("entity"),
+            },
+        );
+        store.add_components(e, TestComponent1::new(5));
+        store.add_components(e, TestComponent2::new(5));
+
+        let (name, comp1) = store
+            .get_components_refs::<(NameComponent, TestComponent1)>(e)
+            .unwrap();
+
+        assert_eq!(name.name, "entity");
+        assert_eq!(*comp1, TestComponent1::new(5));
+    }
+
+    #[test]
+    fn add_components_drops_overwritten_values() {
+        let old = Rc::new(());
+        let new = Rc::new(());
+        let mut store = Store::new();
+        let e = store.create_entity();
+
+        store.add_components(
+            e,
+            DropComponent {
+                counter: old.clone(),
+            },
+        );
+        store.add_components(
+            e,
+            DropComponent {
+                counter: new.clone(),
+            },
+        );
+        assert_eq!(Rc::strong_count(&old), 1);
+
+        store.add_components(
+            e,
+            (
+                TestComponent1::default(),
+                DropComponent {
+                    counter: old.clone(),
+                },
+            ),
+        );
+        assert_eq!(Rc::strong_count(&old), 2);
+        assert_eq!(Rc::strong_count(&new), 1);
+    }
+
+    #[test]
+    fn store_drop_drops_components() {
+        let counter = Rc::new(());
+
+        {
+            let mut store = Store::new();
+            for i in 0..100 {
+                let e = store.create_entity();
+                store.add_components(
+                    e,
+                    (
+                        TestComponent1::new(i),
+                        DropComponent {
+                            counter: counter.clone(),
+                        },
+                    ),
+                );
+            }
+        }
+
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn spawn_batch_values_as_expected() {
+        let mut store = Store::with_capacity(4);
+        let entities = store.spawn_batch(
+            (0..2000).map(|i| (TestComponent1::new(i), TestComponent2::new(i))),
+        );
+
+        assert_eq!(entities.len(), 2000);
+        assert!(store.entities_capacity() >= 2000);
+        for (i, &e) in entities.iter().enumerate() {
+            assert!(store.is_alive(e));
+            let (c1, c2) = store
+                .get_components_refs::<(TestComponent1, TestComponent2)>(e)
+                .unwrap();
+            assert_eq!(*c1, TestComponent1::new(i));
+            assert_eq!(*c2, TestComponent2::new(i));
+        }
+    }
+
+    #[test]
+    fn spawn_batch_fills_partial_page_without_size_hint() {
+        let mut store = Store::new();
+        let first = store.spawn_batch([(TestComponent1::new(0), TestComponent2::new(0))]);
+        let rest = store.spawn_batch(
+            (1..1000)
+                .filter(|i| i % 3 != 0)
+                .map(|i| (TestComponent1::new(i), TestComponent2::new(i))),
+        );
+
+        let mut query = query::component::readonly::<TestComponent1>();
+        assert_eq!(store.component_query_iter(&mut query).len(), 1 + rest.len());
+        for e in first.into_iter().chain(rest) {
+            let (c1, c2) = store
+                .get_components_refs::<(TestComponent1, TestComponent2)>(e)
+                .unwrap();
+            assert_eq!(c1.value as f64 * 2., c2.value);
+        }
+    }
+
+    #[test]
+    fn spawn_batch_reuses_destroyed_ids_and_drops_components() {
+        let counter = Rc::new(());
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.destroy_entity(e);
+
+        let entities = store.spawn_batch((0..10).map(|_| DropComponent {
+            counter: counter.clone(),
+        }));
+        assert_eq!(entities[0].id, e.id);
+        assert_eq!(Rc::strong_count(&counter), 11);
+
+        for e in entities {
+            store.destroy_entity(e);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[derive(Component)]
+    struct MatrixBlock {
+        values: [f32; 8192],
+    }
+
+    #[test]
+    fn component_larger_than_data_page_as_expected() {
+        assert!(std::mem::size_of::<MatrixBlock>() > Store::data_page_size());
+
+        let counter = Rc::new(());
+        let mut store = Store::new();
+        let entities: Vec<_> = (0..5)
+            .map(|i| {
+                let e = store.create_entity();
+                store.add_components(
+                    e,
+                    (
+                        MatrixBlock {
+                            values: [i as f32; 8192],
+                        },
+                        DropComponent {
+                            counter: counter.clone(),
+                        },
+                    ),
+                );
+                e
+            })
+            .collect();
+
+        store.remove_components::<DropComponent>(entities[1]);
+        store.destroy_entity(entities[0]);
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        for (i, &e) in entities.iter().enumerate().skip(1) {
+            let block = store.get_components_refs::<MatrixBlock>(e).unwrap();
+            assert!(block.values.iter().all(|&v| v == i as f32));
+        }
+    }
 }
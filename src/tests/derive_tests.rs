@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use std::{any::TypeId, marker::PhantomData};
+
+    use crate::{ComponentReadOnlyQuery, Reflect, Store};
+
+    mod physics {
+        // Clashes with the derived trait name, which must not be picked by the derive
+        #[allow(dead_code)]
+        pub trait Component {}
+
+        #[derive(crate::Component, Clone, Copy, PartialEq, Debug)]
+        #[component(reflect)]
+        pub struct Velocity<T: Copy>
+        where
+            T: PartialEq,
+        {
+            pub x: T,
+            pub y: T,
+        }
+
+        #[derive(crate::Component, Clone, Copy, PartialEq, Debug)]
+        #[component(reflect)]
+        pub struct Mass(pub f32, pub u8);
+
+        #[derive(crate::Component, Clone, Copy, PartialEq, Debug)]
+        pub enum State {
+            Idle,
+            Moving,
+        }
+
+        #[derive(crate::Tag)]
+        pub struct Layer<T> {
+            _marker: std::marker::PhantomData<T>,
+        }
+    }
+
+    use physics::{Layer, Mass, State, Velocity};
+
+    struct Foreground;
+
+    #[test]
+    fn derive_generic_tuple_and_enum_components() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(
+            e,
+            (
+                Velocity { x: 1f32, y: 2. },
+                Velocity { x: 3, y: 4 },
+                Mass(5., 6),
+                State::Moving,
+            ),
+        );
+        store.add_tag::<Layer<Foreground>>(e);
+
+        assert_eq!(
+            store.get_components_refs::<(Velocity<f32>, Velocity<i32>, Mass, State)>(e),
+            Some((
+                &Velocity { x: 1., y: 2. },
+                &Velocity { x: 3, y: 4 },
+                &Mass(5., 6),
+                &State::Moving
+            ))
+        );
+        assert!(store.has_tag::<Layer<Foreground>>(e));
+        assert!(!store.has_tag::<Layer<Store>>(e));
+
+        let mut query = ComponentReadOnlyQuery::<State>::new();
+        assert_eq!(
+            store.component_query_iter(&mut query).collect::<Vec<_>>(),
+            vec![&State::Moving]
+        );
+        let _ = State::Idle;
+    }
+
+    #[test]
+    fn derive_reflect_generic_and_tuple_structs() {
+        let fields = Velocity::<f64>::reflect_fields();
+        assert_eq!(fields[1].name(), "y");
+        assert_eq!(fields[1].offset(), std::mem::offset_of!(Velocity<f64>, y));
+        assert_eq!(fields[1].type_id(), TypeId::of::<f64>());
+
+        let fields = Mass::reflect_fields();
+        let names: Vec<_> = fields.iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["0", "1"]);
+        assert_eq!(fields[1].type_id(), TypeId::of::<u8>());
+
+        let mut store = Store::new();
+        store.register_reflect::<Mass>();
+        let e = store.create_entity();
+        store.add_components(e, Mass(1., 2));
+        *store
+            .get_field_mut::<u8>(e, TypeId::of::<Mass>(), "1")
+            .unwrap() = 3;
+        assert_eq!(store.get_components_refs::<Mass>(e), Some(&Mass(1., 3)));
+
+        let _ = PhantomData::<Foreground>;
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{Component, ComponentType, DynamicQuery, Store};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe fn count_drop(_: *mut u8) {
+        DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    unsafe fn forget(_: *mut u8) {}
+
+    unsafe fn copy_u32(src: *const u8, dst: *mut u8) {
+        src.copy_to_nonoverlapping(dst, 4);
+    }
+
+    #[test]
+    fn add_and_read_component_bytes() {
+        let mut store = Store::new();
+        let health = ComponentType::dynamic("Health", 4, 4, None, None);
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 1., y: 2. });
+
+        unsafe {
+            store.add_component_bytes(e, &health, &10u32.to_ne_bytes());
+        }
+
+        assert_eq!(health.name(), "Health");
+        assert!(store
+            .get_entity_archetype(e)
+            .unwrap()
+            .has_component::<Position>());
+        assert_eq!(
+            store.get_component_bytes(e, &health),
+            Some(&10u32.to_ne_bytes()[..])
+        );
+        assert_eq!(
+            store.get_components_refs::<Position>(e),
+            Some(&Position { x: 1., y: 2. })
+        );
+
+        unsafe {
+            store
+                .get_component_bytes_mut(e, &health)
+                .unwrap()
+                .copy_from_slice(&20u32.to_ne_bytes());
+        }
+        assert_eq!(
+            store.get_component_bytes(e, &health),
+            Some(&20u32.to_ne_bytes()[..])
+        );
+
+        let same = ComponentType::dynamic("Health", 4, 4, None, None);
+        assert_eq!(same, health);
+        assert_eq!(
+            store.get_component_bytes(e, &same),
+            Some(&20u32.to_ne_bytes()[..])
+        );
+    }
+
+    #[test]
+    fn dynamic_component_drop_fn_is_called() {
+        let mut store = Store::new();
+        let handle = ComponentType::dynamic("Handle", 8, 8, Some(count_drop), None);
+        let e = store.create_entity();
+        let before = DROPPED.load(Ordering::Relaxed);
+
+        unsafe {
+            store.add_component_bytes(e, &handle, &1u64.to_ne_bytes());
+            store.add_component_bytes(e, &handle, &2u64.to_ne_bytes());
+        }
+        assert_eq!(DROPPED.load(Ordering::Relaxed) - before, 1);
+
+        store.add_components(e, Position { x: 0., y: 0. });
+        store.remove_component_type(e, &handle);
+        assert_eq!(DROPPED.load(Ordering::Relaxed) - before, 2);
+        assert_eq!(store.get_component_bytes(e, &handle), None);
+        assert!(store.get_components_refs::<Position>(e).is_some());
+    }
+
+    #[test]
+    fn dynamic_query_reads_and_writes_columns() {
+        let mut store = Store::new();
+        let speed = ComponentType::dynamic("Speed", 4, 4, None, None);
+        let frozen = ComponentType::dynamic("Frozen", 0, 1, None, None);
+        let distance = ComponentType::dynamic("Distance", 4, 4, None, None);
+
+        for i in 0..10u32 {
+            let e = store.create_entity();
+            store.add_components(e, Position { x: 0., y: 0. });
+            unsafe {
+                store.add_component_bytes(e, &speed, &(i as f32).to_ne_bytes());
+                store.add_component_bytes(e, &distance, &0f32.to_ne_bytes());
+                if i % 2 == 1 {
+                    store.add_component_bytes(e, &frozen, &[]);
+                }
+            }
+        }
+
+        let mut query = DynamicQuery::new()
+            .read(&[speed])
+            .write(&[distance])
+            .without(&[frozen]);
+        let mut count = 0;
+        for mut page in store.dynamic_query_iter(&mut query) {
+            let speeds: Vec<f32> = page
+                .column(0)
+                .chunks_exact(4)
+                .map(|b| f32::from_ne_bytes(b.try_into().unwrap()))
+                .collect();
+            let distances = unsafe { page.column_mut(0) };
+            assert_eq!(distances.len(), speeds.len() * distance.size());
+
+            for (i, speed) in speeds.into_iter().enumerate() {
+                distances[i * 4..i * 4 + 4].copy_from_slice(&(speed * 2.).to_ne_bytes());
+            }
+            count += page.len();
+        }
+        assert_eq!(count, 5);
+
+        let mut query = DynamicQuery::new().read(&[distance]);
+        let mut values: Vec<f32> = store
+            .dynamic_query_iter(&mut query)
+            .flat_map(|page| {
+                page.column(0)
+                    .chunks_exact(4)
+                    .map(|b| f32::from_ne_bytes(b.try_into().unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        values.sort_by(f32::total_cmp);
+        assert_eq!(values, vec![0., 0., 0., 0., 0., 0., 4., 8., 12., 16.]);
+    }
+
+    #[test]
+    fn dynamic_query_entities_and_new_archetypes() {
+        let mut store = Store::new();
+        let tag = ComponentType::dynamic("Scripted", 1, 1, None, None);
+        let mut query = DynamicQuery::new().with(&[tag]);
+
+        let e0 = store.create_entity();
+        unsafe { store.add_component_bytes(e0, &tag, &[1]) };
+        assert_eq!(store.dynamic_query_iter(&mut query).count(), 1);
+
+        let e1 = store.create_entity();
+        store.add_components(e1, Position { x: 0., y: 0. });
+        unsafe { store.add_component_bytes(e1, &tag, &[2]) };
+
+        let mut entities: Vec<_> = store
+            .dynamic_query_iter(&mut query)
+            .flat_map(|page| page.entities().collect::<Vec<_>>())
+            .collect();
+        entities.sort_by_key(|e| e.id);
+        assert_eq!(entities, vec![e0, e1]);
+    }
+
+    #[test]
+    fn clone_entity_uses_clone_fn_of_descriptor() {
+        let mut store = Store::new();
+        let script = ComponentType::dynamic("Script", 4, 4, Some(forget), Some(copy_u32));
+        let e = store.create_entity();
+        unsafe { store.add_component_bytes(e, &script, &7u32.to_ne_bytes()) };
+
+        let clone = store.clone_entity(e);
+
+        assert_eq!(
+            store.get_component_bytes(clone, &script),
+            Some(&7u32.to_ne_bytes()[..])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Bytes length doesn't match")]
+    fn add_component_bytes_with_wrong_length_panics() {
+        let mut store = Store::new();
+        let health = ComponentType::dynamic("Health", 4, 4, None, None);
+        let e = store.create_entity();
+
+        unsafe { store.add_component_bytes(e, &health, &[0; 2]) };
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered with other layout")]
+    fn dynamic_type_with_same_name_and_other_layout_panics() {
+        ComponentType::dynamic("Mana", 4, 4, None, None);
+        ComponentType::dynamic("Mana", 8, 4, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered with other glue")]
+    fn dynamic_type_with_same_name_and_other_glue_panics() {
+        ComponentType::dynamic("Shield", 4, 4, None, Some(copy_u32));
+        ComponentType::dynamic("Shield", 4, 4, Some(forget), Some(copy_u32));
+    }
+
+    #[test]
+    #[should_panic(expected = "only dynamic types are accessed as bytes")]
+    fn get_component_bytes_of_rust_type_panics() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 1., y: 2. });
+
+        store.get_component_bytes(e, &ComponentType::of::<Position>());
+    }
+
+    #[test]
+    #[should_panic(expected = "only dynamic types are accessed as bytes")]
+    fn dynamic_query_write_of_rust_type_panics() {
+        DynamicQuery::new().write(&[ComponentType::of::<Position>()]);
+    }
+}
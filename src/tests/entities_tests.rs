@@ -13,8 +13,8 @@ mod tests {
     fn create_entity_with_archetype_returns_with_new_id_and_first_version() {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::new();
-        let e1 = reg.create_entity_with_archetype(&arch_stub);
-        let e2 = reg.create_entity_with_archetype(&arch_stub);
+        let e1 = unsafe { reg.create_entity_with_archetype(&arch_stub) };
+        let e2 = unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         assert_eq!(e1.id(), 0);
         assert_eq!(e1.version(), 1);
@@ -26,11 +26,11 @@ mod tests {
     fn create_entity_with_archetype_oversized_will_increase_capacity() {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::with_capacity(1);
-        reg.create_entity_with_archetype(&arch_stub);
+        unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         assert_eq!(reg.entities_capacity(), 1);
 
-        reg.create_entity_with_archetype(&arch_stub);
+        unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         assert_ne!(reg.entities_capacity(), 1);
     }
@@ -73,10 +73,10 @@ mod tests {
     fn destroy_entity_when_already_destroyed_panics() {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::new();
-        let e1 = reg.create_entity_with_archetype(&arch_stub);
+        let e1 = unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         reg.destroy_entity(e1);
-        reg.create_entity_with_archetype(&arch_stub);
+        unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         reg.destroy_entity(e1)
     }
@@ -89,7 +89,8 @@ mod tests {
         let mut reg = Store::with_capacity(1);
         let mut reg2 = Store::with_capacity(2);
 
-        let entity_with_invalid_id = reg2.create_entity_with_archetype(&arch_stub);
+        let entity_with_invalid_id =
+            unsafe { reg2.create_entity_with_archetype(&arch_stub) };
 
         reg.destroy_entity(entity_with_invalid_id);
     }
@@ -98,9 +99,9 @@ mod tests {
     fn destroy_entity_will_increase_version_for_entity_with_same_id() {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::new();
-        let e1 = reg.create_entity_with_archetype(&arch_stub);
+        let e1 = unsafe { reg.create_entity_with_archetype(&arch_stub) };
         reg.destroy_entity(e1);
-        let e1_1 = reg.create_entity_with_archetype(&arch_stub);
+        let e1_1 = unsafe { reg.create_entity_with_archetype(&arch_stub) };
 
         assert_eq!(e1.id(), e1_1.id());
         assert_eq!(e1_1.version(), 2);
@@ -112,7 +113,7 @@ mod tests {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::new();
 
-        let e = reg.create_entity_with_archetype(&arch_stub);
+        let e = unsafe { reg.create_entity_with_archetype(&arch_stub) };
         assert!(reg.is_alive(e));
 
         reg.destroy_entity(e);
@@ -123,10 +124,10 @@ mod tests {
     fn destroy_will_not_affect_other_entities() {
         let arch_stub = create_archetype_stub();
         let mut reg = Store::new();
-        let e = reg.create_entity_with_archetype(&arch_stub);
+        let e = unsafe { reg.create_entity_with_archetype(&arch_stub) };
         assert!(reg.is_alive(e));
 
-        let entity_to_check = reg.create_entity_with_archetype(&arch_stub);
+        let entity_to_check = unsafe { reg.create_entity_with_archetype(&arch_stub) };
         assert!(reg.is_alive(e));
         assert!(reg.is_alive(entity_to_check));
 
@@ -142,7 +143,7 @@ mod tests {
         let mut entities = Vec::new();
 
         for _ in 0..33 {
-            let e = reg.create_entity_with_archetype(&arch_stub);
+            let e = unsafe { reg.create_entity_with_archetype(&arch_stub) };
             assert!(reg.is_alive(e));
 
             entities.push(e);
@@ -156,8 +157,8 @@ mod tests {
         let arch = Archetype::with_components::<(u64, u32)>();
         let mut store = Store::new();
 
-        let e0 = store.create_entity_with_archetype(&arch);
-        let e1 = store.create_entity_with_archetype(&arch);
+        let e0 = unsafe { store.create_entity_with_archetype(&arch) };
+        let e1 = unsafe { store.create_entity_with_archetype(&arch) };
 
         let arch0 = store.get_entity_archetype(e0).unwrap();
         let arch1 = store.get_entity_archetype(e1).unwrap();
@@ -172,8 +173,8 @@ mod tests {
 
         let mut store = Store::new();
 
-        let e0 = store.create_entity_with_archetype(&arch0);
-        let e1 = store.create_entity_with_archetype(&arch1);
+        let e0 = unsafe { store.create_entity_with_archetype(&arch0) };
+        let e1 = unsafe { store.create_entity_with_archetype(&arch1) };
 
         let arch0 = store.get_entity_archetype(e0).unwrap();
         let arch1 = store.get_entity_archetype(e1).unwrap();
@@ -26,7 +26,7 @@ mod test {
 
         let create_valid_entities = |i| {
             let mut store = store.borrow_mut();
-            let e = store.create_entity_with_archetype(&valid_arch);
+            let e = unsafe { store.create_entity_with_archetype(&valid_arch) };
             let (comp1, comp2) = store
                 .get_components_refs_mut::<(TestComponent1, TestComponent2)>(e)
                 .unwrap();
@@ -44,7 +44,7 @@ mod test {
 
         valid_entities.extend((0..7).map(create_valid_entities));
 
-        invalid_entities.extend((7..15).map(|_| {
+        invalid_entities.extend((7..15).map(|_| unsafe {
             store
                 .borrow_mut()
                 .create_entity_with_archetype(&invalid_arch)
@@ -150,7 +150,7 @@ mod test {
         entities: &mut Vec<Entity>,
         component_values: &mut Vec<TestComponent2>,
     ) {
-        let e = store.create_entity_with_archetype(&archetype);
+        let e = unsafe { store.create_entity_with_archetype(&archetype) };
         let comp = store.get_components_refs_mut::<TestComponent2>(e).unwrap();
 
         *comp = TestComponent2 { value: e.id as f64 };
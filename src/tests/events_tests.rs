@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Events, Store};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Collision {
+        id: u32,
+    }
+
+    fn read_ids(
+        events: &Events<Collision>,
+        reader: &mut crate::EventReader<Collision>,
+    ) -> Vec<u32> {
+        events.read(reader).map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn events_are_read_once_per_reader() {
+        let mut events = Events::new();
+        let mut first = events.reader();
+        let mut second = events.reader();
+
+        events.send(Collision { id: 0 });
+        events.send(Collision { id: 1 });
+        assert_eq!(read_ids(&events, &mut first), vec![0, 1]);
+
+        events.update();
+        events.send(Collision { id: 2 });
+
+        assert_eq!(read_ids(&events, &mut first), vec![2]);
+        assert_eq!(read_ids(&events, &mut first), Vec::<u32>::new());
+        assert_eq!(second.unread_count(&events), 3);
+        assert_eq!(read_ids(&events, &mut second), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn events_expire_after_two_updates() {
+        let mut events = Events::new();
+        let mut reader = events.reader();
+
+        events.send(Collision { id: 0 });
+        events.update();
+        events.send(Collision { id: 1 });
+        events.update();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(read_ids(&events, &mut reader), vec![1]);
+
+        events.update();
+        assert!(events.is_empty());
+
+        let mut late_reader = events.reader_from_now();
+        events.send(Collision { id: 2 });
+        assert_eq!(read_ids(&events, &mut late_reader), vec![2]);
+    }
+
+    #[test]
+    fn events_drain_and_clear_as_expected() {
+        let mut events = Events::new();
+        let mut reader = events.reader();
+        events.send_batch((0..3).map(|id| Collision { id }));
+        events.update();
+        events.send(Collision { id: 3 });
+
+        let drained: Vec<_> = events.drain().map(|e| e.id).collect();
+        assert_eq!(drained, vec![0, 1, 2, 3]);
+        assert!(read_ids(&events, &mut reader).is_empty());
+
+        events.send(Collision { id: 4 });
+        events.clear();
+        events.send(Collision { id: 5 });
+        assert_eq!(read_ids(&events, &mut reader), vec![5]);
+    }
+
+    #[test]
+    fn store_events_are_updated_together() {
+        let mut store = Store::new();
+        assert!(store.events::<Collision>().is_none());
+
+        store.add_events::<u32>();
+        store.send_event(Collision { id: 0 });
+        let mut reader = store.events::<Collision>().unwrap().reader();
+
+        store.update_events();
+        store.update_events();
+        store.send_event(Collision { id: 1 });
+        store.events_mut::<u32>().unwrap().send(10);
+
+        let events = store.events::<Collision>().unwrap();
+        assert_eq!(read_ids(events, &mut reader), vec![1]);
+        assert_eq!(store.events::<u32>().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::{Component, Entity, Store};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Transform {
+        x: f32,
+    }
+
+    fn create_tree(store: &mut Store) -> [Entity; 5] {
+        let root = store.create_entity();
+        let a = store.create_entity();
+        let b = store.create_entity();
+        let a_child = store.create_entity();
+        let b_child = store.create_entity();
+
+        store.set_parent(a, root);
+        store.set_parent(b, root);
+        store.set_parent(a_child, a);
+        store.set_parent(b_child, b);
+
+        [root, a, b, a_child, b_child]
+    }
+
+    #[test]
+    fn parent_and_children_are_linked() {
+        let mut store = Store::new();
+        let [root, a, b, a_child, _] = create_tree(&mut store);
+
+        assert_eq!(store.parent(root), None);
+        assert_eq!(store.parent(a), Some(root));
+        assert_eq!(store.parent(a_child), Some(a));
+        assert_eq!(store.children(root).collect::<Vec<_>>(), vec![a, b]);
+
+        store.set_parent(a_child, b);
+        assert_eq!(store.children(a).count(), 0);
+        assert_eq!(store.parent(a_child), Some(b));
+
+        store.remove_parent(a);
+        assert_eq!(store.parent(a), None);
+        assert_eq!(store.children(root).collect::<Vec<_>>(), vec![b]);
+    }
+
+    #[test]
+    fn hierarchy_iter_walks_depth_first() {
+        let mut store = Store::new();
+        let [root, a, b, a_child, b_child] = create_tree(&mut store);
+
+        assert_eq!(
+            store.hierarchy_iter(root).collect::<Vec<_>>(),
+            vec![root, a, a_child, b, b_child]
+        );
+        assert_eq!(
+            store.hierarchy_iter(b).collect::<Vec<_>>(),
+            vec![b, b_child]
+        );
+        assert_eq!(
+            store.hierarchy_iter(b_child).collect::<Vec<_>>(),
+            vec![b_child]
+        );
+    }
+
+    #[test]
+    fn destroy_entity_destroys_descendants() {
+        let mut store = Store::new();
+        let [root, a, b, a_child, b_child] = create_tree(&mut store);
+        store.add_components(a_child, Transform { x: 1. });
+
+        store.destroy_entity(a);
+
+        assert!(!store.is_alive(a));
+        assert!(!store.is_alive(a_child));
+        assert!(store.is_alive(b_child));
+        assert_eq!(store.children(root).collect::<Vec<_>>(), vec![b]);
+
+        store.destroy_entity(root);
+        assert!(!store.is_alive(b));
+        assert!(!store.is_alive(b_child));
+
+        let reused = store.create_entity();
+        assert_eq!(store.parent(reused), None);
+        assert_eq!(store.children(reused).count(), 0);
+    }
+
+    #[test]
+    fn destroy_dead_entity_keeps_reused_id() {
+        let mut store = Store::new();
+        let stale = store.create_entity();
+        store.destroy_entity(stale);
+
+        let reused = store.create_entity();
+        assert_eq!(reused.id, stale.id);
+        let child = store.create_entity();
+        store.set_parent(child, reused);
+        store.add_components(reused, Transform { x: 1. });
+
+        let _ = catch_unwind(AssertUnwindSafe(|| store.destroy_entity(stale)));
+
+        assert!(store.is_alive(reused));
+        assert!(store.is_alive(child));
+        assert_eq!(store.children(reused).collect::<Vec<_>>(), vec![child]);
+        assert_eq!(
+            store.get_components_refs::<Transform>(reused),
+            Some(&Transform { x: 1. })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_parent_panics_on_cycle() {
+        let mut store = Store::new();
+        let [root, _, _, a_child, _] = create_tree(&mut store);
+
+        store.set_parent(root, a_child);
+    }
+
+    #[test]
+    fn hierarchy_survives_entities_grow() {
+        let mut store = Store::with_capacity(1);
+        let root = store.create_entity();
+        let children: Vec<_> = (0..100)
+            .map(|_| {
+                let child = store.create_entity();
+                store.set_parent(child, root);
+                child
+            })
+            .collect();
+
+        assert_eq!(store.children(root).collect::<Vec<_>>(), children);
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{Component, Entity, Store, Tag};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Body {
+        handle: u32,
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Mesh {
+        handle: u32,
+    }
+
+    #[derive(Tag)]
+    struct Selected;
+
+    type Log = Rc<RefCell<Vec<(Entity, u32)>>>;
+
+    fn body_log(store: &mut Store) -> (Log, Log) {
+        let added = Log::default();
+        let removed = Log::default();
+
+        let log = added.clone();
+        store.on_add::<Body>(move |e, body| log.borrow_mut().push((e, body.handle)));
+        let log = removed.clone();
+        store.on_remove::<Body>(move |e, body| log.borrow_mut().push((e, body.handle)));
+
+        (added, removed)
+    }
+
+    #[test]
+    fn on_add_is_called_only_for_new_components() {
+        let mut store = Store::new();
+        let (added, _) = body_log(&mut store);
+
+        let e = store.create_entity();
+        store.add_components(e, Mesh { handle: 1 });
+        store.add_components(e, (Body { handle: 2 }, Mesh { handle: 3 }));
+        store.add_components(e, Body { handle: 4 });
+        let spawned = store.spawn_batch([Body { handle: 5 }]);
+
+        assert_eq!(*added.borrow(), vec![(e, 2), (spawned[0], 5)]);
+    }
+
+    #[test]
+    fn on_remove_is_called_before_remove_and_destroy() {
+        let mut store = Store::new();
+        let (_, removed) = body_log(&mut store);
+        let destroyed = Rc::new(RefCell::new(Vec::new()));
+        let log = destroyed.clone();
+        store.on_entity_destroyed(move |e| log.borrow_mut().push(e));
+
+        let e0 = store.create_entity();
+        store.add_components(e0, (Body { handle: 1 }, Mesh { handle: 1 }));
+        let e1 = store.create_entity();
+        store.add_components(e1, Body { handle: 2 });
+
+        store.remove_components::<Body>(e0);
+        store.remove_components::<Body>(e0);
+        store.destroy_entity(e1);
+
+        assert_eq!(*removed.borrow(), vec![(e0, 1), (e1, 2)]);
+        assert_eq!(*destroyed.borrow(), vec![e1]);
+    }
+
+    #[test]
+    fn destroy_hooks_are_called_for_descendants() {
+        let mut store = Store::new();
+        let destroyed = Rc::new(RefCell::new(Vec::new()));
+        let log = destroyed.clone();
+        store.on_entity_destroyed(move |e| log.borrow_mut().push(e));
+
+        let parent = store.create_entity();
+        let child = store.create_entity();
+        store.set_parent(child, parent);
+        store.destroy_entity(parent);
+
+        assert_eq!(*destroyed.borrow(), vec![child, parent]);
+    }
+
+    #[test]
+    fn tag_hooks_are_called_on_changes_only() {
+        let mut store = Store::new();
+        let added = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+        let log = added.clone();
+        store.on_tag_added::<Selected>(move |e| log.borrow_mut().push(e));
+        let log = removed.clone();
+        store.on_tag_removed::<Selected>(move |e| log.borrow_mut().push(e));
+
+        let e0 = store.create_entity();
+        let e1 = store.create_entity();
+        store.add_tag::<Selected>(e0);
+        store.add_tag::<Selected>(e0);
+        store.add_tag::<Selected>(e1);
+        store.remove_tag::<Selected>(e0);
+        store.remove_tag::<Selected>(e0);
+        store.destroy_entity(e1);
+        store.destroy_entity(e0);
+
+        assert_eq!(*added.borrow(), vec![e0, e1]);
+        assert_eq!(*removed.borrow(), vec![e0, e1]);
+    }
+}
@@ -1,9 +1,23 @@
 mod archetype_tests;
+mod change_detection_tests;
+mod command_buffer_tests;
 mod components_tests;
 mod component_query_test;
+mod derive_tests;
+mod dynamic_component_tests;
 mod entities_tests;
 mod entity_component_query_test;
+mod events_tests;
+mod hierarchy_tests;
+mod lifecycle_hooks_tests;
 mod ordered_component_query_tests;
 mod order_group_tests;
+mod prefab_tests;
+mod reflection_tests;
 mod resources_store;
+mod resources_tests;
+mod schedule_tests;
+mod serialization_tests;
+mod store_clone_tests;
+mod store_config_tests;
 mod tag_tests;
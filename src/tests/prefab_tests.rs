@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use crate::{Component, ComponentReadOnlyQuery, Prefab, Store, Tag};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Health {
+        value: u32,
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Speed {
+        value: f64,
+    }
+
+    #[derive(Component, Clone)]
+    struct Sprite {
+        texture: Rc<String>,
+    }
+
+    #[derive(Component, Clone, Copy)]
+    struct Marker;
+
+    #[derive(Tag)]
+    struct Enemy;
+
+    #[derive(Tag)]
+    #[tag(archetype)]
+    struct Boss;
+
+    #[test]
+    fn instantiate_writes_prototype_components_and_tags() {
+        let mut store = Store::new();
+        let prefab = Prefab::new((Health { value: 100 }, Speed { value: 2.5 }))
+            .with_tag::<Enemy>();
+
+        let entities = store.instantiate(&prefab, 1000);
+
+        assert_eq!(entities.len(), 1000);
+        assert_eq!(store.count_tagged::<Enemy>(), 1000);
+        assert!(entities.iter().all(|&e| {
+            store.get_components_refs::<(Health, Speed)>(e)
+                == Some((&Health { value: 100 }, &Speed { value: 2.5 }))
+        }));
+
+        let mut query = ComponentReadOnlyQuery::<(Health, Speed)>::new();
+        assert_eq!(store.component_query_iter(&mut query).count(), 1000);
+    }
+
+    #[test]
+    fn instantiate_clones_registered_components() {
+        let texture = Rc::new(String::from("enemy.png"));
+        let mut store = Store::new();
+        store.register_clone::<Sprite>();
+        let prefab = Prefab::new((
+            Health { value: 10 },
+            Sprite {
+                texture: texture.clone(),
+            },
+        ));
+
+        let entities = store.instantiate(&prefab, 10);
+        let sprite = store.get_components_refs::<Sprite>(entities[9]).unwrap();
+        assert_eq!(*sprite.texture, "enemy.png");
+        assert_eq!(Rc::strong_count(&texture), 12);
+
+        drop(prefab);
+        drop(store);
+        assert_eq!(Rc::strong_count(&texture), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be cloned without Store::register_clone")]
+    fn instantiate_with_unregistered_drop_component_panics() {
+        let mut store = Store::new();
+        let prefab = Prefab::new(Sprite {
+            texture: Rc::new(String::new()),
+        });
+
+        store.instantiate(&prefab, 1);
+    }
+
+    #[test]
+    fn instantiate_zero_sized_prefab() {
+        let mut store = Store::new();
+        let prefab = Prefab::new(Marker);
+
+        let entities = store.instantiate(&prefab, 3);
+
+        assert!(prefab.archetype().has_component::<Marker>());
+        assert!(entities
+            .iter()
+            .all(|&e| store.get_components_refs::<Marker>(e).is_some()));
+    }
+
+    #[test]
+    fn prefab_archetype_tag_is_stored_in_instances_archetype() {
+        let mut store = Store::new();
+        let prefab = Prefab::new(Health { value: 7 })
+            .with_tag::<Boss>()
+            .with_tag::<Boss>();
+
+        let entities = store.instantiate(&prefab, 10);
+        let mut query = ComponentReadOnlyQuery::<Health>::new().with::<Boss>();
+        let values: Vec<_> = store
+            .component_query_iter(&mut query)
+            .map(|h| h.value)
+            .collect();
+
+        assert!(prefab.archetype().has_component::<Boss>());
+        assert_eq!(values, vec![7; 10]);
+        assert!(entities.iter().all(|&e| store.has_tag::<Boss>(e)));
+    }
+
+    /// Clone panics, when the clones limit is exhausted
+    #[derive(Component)]
+    struct Limited {
+        clones_left: Rc<Cell<u32>>,
+    }
+
+    impl Clone for Limited {
+        fn clone(&self) -> Self {
+            let clones_left = self.clones_left.get();
+            assert!(clones_left > 0, "Clones limit is exhausted");
+            self.clones_left.set(clones_left - 1);
+
+            Self {
+                clones_left: self.clones_left.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn instantiate_panic_keeps_created_instances() {
+        let texture = Rc::new(String::from("enemy.png"));
+        let mut store = Store::new();
+        store.register_clone::<Sprite>();
+        store.register_clone::<Limited>();
+        let prefab = Prefab::new((
+            Sprite {
+                texture: texture.clone(),
+            },
+            Limited {
+                clones_left: Rc::new(Cell::new(2)),
+            },
+        ));
+
+        let result = catch_unwind(AssertUnwindSafe(|| store.instantiate(&prefab, 5)));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&texture), 4);
+
+        let mut query = ComponentReadOnlyQuery::<Sprite>::new();
+        assert_eq!(store.component_query_iter(&mut query).count(), 2);
+
+        drop(store);
+        assert_eq!(Rc::strong_count(&texture), 2);
+    }
+}
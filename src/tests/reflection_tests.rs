@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use crate::{Component, ComponentType, Reflect, Store};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    #[component(reflect)]
+    struct Transform {
+        x: f32,
+        y: f32,
+        layer: u8,
+    }
+
+    #[derive(Component, Clone, Copy)]
+    #[component(reflect)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy)]
+    struct Hidden {
+        _value: u64,
+    }
+
+    fn spawn(store: &mut Store) -> crate::Entity {
+        let e = store.create_entity();
+        store.add_components(
+            e,
+            (
+                Transform {
+                    x: 1.,
+                    y: 2.,
+                    layer: 3,
+                },
+                Health(100),
+                Hidden { _value: 0 },
+            ),
+        );
+
+        e
+    }
+
+    #[test]
+    fn derive_generates_fields_metadata() {
+        let fields = Transform::reflect_fields();
+
+        let names: Vec<_> = fields.iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["x", "y", "layer"]);
+        assert_eq!(fields[1].offset(), std::mem::offset_of!(Transform, y));
+        assert_eq!(fields[2].type_id(), TypeId::of::<u8>());
+        assert_eq!(fields[2].type_name(), "u8");
+        assert_eq!(fields[0].size(), 4);
+
+        let fields = Health::reflect_fields();
+        assert_eq!(fields[0].name(), "0");
+        assert_eq!(fields[0].type_id(), TypeId::of::<u32>());
+    }
+
+    #[test]
+    fn reflect_components_lists_registered_components() {
+        let mut store = Store::new();
+        store.register_reflect::<Transform>();
+        store.register_reflect::<Health>();
+        let e = spawn(&mut store);
+
+        let mut names: Vec<_> = store
+            .reflect_components(e)
+            .iter()
+            .map(|r| r.name().rsplit("::").next().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Health", "Transform"]);
+
+        let reflection = store
+            .get_component_reflection(TypeId::of::<Transform>())
+            .unwrap();
+        assert_eq!(reflection.size(), ComponentType::of::<Transform>().size());
+        assert!(reflection.field("layer").is_some());
+        assert!(store
+            .get_component_reflection(TypeId::of::<Hidden>())
+            .is_none());
+    }
+
+    #[test]
+    fn read_and_write_fields_by_name() {
+        let mut store = Store::new();
+        store.register_reflect::<Transform>();
+        let e = spawn(&mut store);
+        let transform = TypeId::of::<Transform>();
+
+        assert_eq!(store.get_field::<f32>(e, transform, "y"), Some(&2.));
+        assert_eq!(
+            unsafe { store.get_field_bytes(e, transform, "layer") },
+            Some(&[3u8][..])
+        );
+        assert_eq!(store.get_field::<f32>(e, transform, "z"), None);
+        assert_eq!(store.get_field::<u32>(e, TypeId::of::<Health>(), "0"), None);
+
+        *store.get_field_mut::<f32>(e, transform, "x").unwrap() = 10.;
+        assert_eq!(
+            store.get_components_refs::<Transform>(e),
+            Some(&Transform {
+                x: 10.,
+                y: 2.,
+                layer: 3
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field y is f32, not u32")]
+    fn get_field_with_wrong_type_panics() {
+        let mut store = Store::new();
+        store.register_reflect::<Transform>();
+        let e = spawn(&mut store);
+
+        store.get_field::<u32>(e, TypeId::of::<Transform>(), "y");
+    }
+}
@@ -89,4 +89,85 @@ mod tests {
 
         assert!(DropCounter::drop_counts_eq(EXPECTED_DROPS))
     }
+
+    #[test]
+    fn resources_store_get_stale_handle_returns_none() {
+        let mut store = ResourceStore::new();
+        let e = store.create(String::from(EXPECTED_STRING));
+        store.release(e);
+        let reused = store.create(String::from("Reused"));
+
+        assert_eq!(reused.id, e.id);
+        assert!(!store.contains(e));
+        assert_eq!(store.get(e), None);
+        assert_eq!(store.get_mut(e), None);
+        assert_eq!(store.try_release(e), None);
+        assert_eq!(store.get(reused).map(String::as_str), Some("Reused"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Resource entity is not alive")]
+    fn resources_store_index_stale_handle_panics() {
+        let mut store = ResourceStore::new();
+        let e = store.create(String::from(EXPECTED_STRING));
+        store.release(e);
+
+        let _ = &store[e];
+    }
+
+    #[test]
+    fn resources_store_iter_yields_alive_resources() {
+        let mut store = ResourceStore::new();
+        let entities: Vec<_> = (0..5).map(|i| store.create(i)).collect();
+        store.release(entities[1]);
+        store.release(entities[3]);
+
+        assert_eq!(store.len(), 3);
+        let items: Vec<_> = store.iter().map(|(e, v)| (e, *v)).collect();
+        assert_eq!(
+            items,
+            vec![(entities[0], 0), (entities[2], 2), (entities[4], 4)]
+        );
+
+        for (_, v) in store.iter_mut() {
+            *v *= 10;
+        }
+        assert_eq!(store[entities[4]], 40);
+    }
+
+    #[test]
+    fn resources_store_retain_drops_rejected_resources() {
+        DropCounter::reset();
+
+        let mut store = ResourceStore::new();
+        let entities: Vec<_> = (0..4).map(|_| store.create(DropCounter)).collect();
+        store.retain(|e, _| e.id % 2 == 0);
+
+        assert!(DropCounter::drop_counts_eq(2));
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(entities[0]));
+        assert!(!store.contains(entities[1]));
+    }
+
+    #[test]
+    fn resources_store_drain_empties_store() {
+        DropCounter::reset();
+
+        let mut store = ResourceStore::new();
+        let entities: Vec<_> = (0..3).map(|_| store.create(DropCounter)).collect();
+        {
+            let mut drain = store.drain();
+            let (e, _) = drain.next().unwrap();
+            assert_eq!(e, entities[0]);
+        }
+
+        assert!(DropCounter::drop_counts_eq(3));
+        assert!(store.is_empty());
+        assert!(entities.iter().all(|&e| !store.contains(e)));
+
+        store.create(DropCounter);
+        store.clear();
+        assert!(DropCounter::drop_counts_eq(4));
+        assert_eq!(store.iter().count(), 0);
+    }
 }
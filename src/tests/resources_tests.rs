@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Component, ComponentReadOnlyQuery, Store};
+
+    #[derive(Debug, PartialEq)]
+    struct Time {
+        delta: f32,
+    }
+
+    #[derive(Component, Clone, Copy)]
+    struct Velocity {
+        value: f32,
+    }
+
+    #[test]
+    fn insert_and_remove_resource_as_expected() {
+        let mut store = Store::new();
+        assert!(store.resource::<Time>().is_none());
+
+        assert_eq!(store.insert_resource(Time { delta: 0.1 }), None);
+        assert_eq!(
+            store.insert_resource(Time { delta: 0.2 }),
+            Some(Time { delta: 0.1 })
+        );
+        assert!(store.contains_resource::<Time>());
+        assert_eq!(store.resource::<Time>().unwrap().delta, 0.2);
+
+        assert_eq!(store.remove_resource::<Time>(), Some(Time { delta: 0.2 }));
+        assert!(!store.contains_resource::<Time>());
+        assert_eq!(store.remove_resource::<Time>(), None);
+    }
+
+    #[test]
+    fn resource_is_borrowed_while_query_is_iterated() {
+        let mut store = Store::new();
+        store.insert_resource(Time { delta: 0.5 });
+        store.insert_resource(0.0f32);
+        for value in 1..=3 {
+            let e = store.create_entity();
+            store.add_components(
+                e,
+                Velocity {
+                    value: value as f32,
+                },
+            );
+        }
+
+        let mut query = ComponentReadOnlyQuery::<Velocity>::new();
+        let time = store.resource::<Time>().unwrap();
+        let mut distance = store.resource_mut::<f32>().unwrap();
+        for velocity in store.component_query_iter(&mut query) {
+            *distance += velocity.value * time.delta;
+        }
+        drop(distance);
+
+        assert_eq!(*store.resource::<f32>().unwrap(), 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_resource_borrow_panics() {
+        let mut store = Store::new();
+        store.insert_resource(Time { delta: 0.5 });
+
+        let _time = store.resource::<Time>();
+        let _time_mut = store.resource_mut::<Time>();
+    }
+}
@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        query, Component, ReadWriteAccess, ReadonlyAccess, Schedule, Store, SystemAccess,
+        WriteAccess,
+    };
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Position {
+        x: f32,
+    }
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Velocity {
+        x: f32,
+    }
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Health {
+        value: i32,
+    }
+
+    #[test]
+    fn system_access_conflicts_as_expected() {
+        let read_pos = SystemAccess::of::<ReadonlyAccess<Position>>();
+        let write_pos = SystemAccess::of::<WriteAccess<Position>>();
+        let read_vel_write_pos =
+            SystemAccess::of::<ReadWriteAccess<Velocity, Position>>();
+        let write_health = SystemAccess::of::<WriteAccess<Health>>();
+
+        assert!(!read_pos.is_conflict_with(&read_pos));
+        assert!(read_pos.is_conflict_with(&write_pos));
+        assert!(write_pos.is_conflict_with(&read_pos));
+        assert!(read_vel_write_pos.is_conflict_with(&write_pos));
+        assert!(!write_health.is_conflict_with(&read_vel_write_pos));
+    }
+
+    struct Score {
+        value: i32,
+    }
+
+    #[test]
+    fn resource_access_conflicts_as_expected() {
+        let read_score = SystemAccess::new().read_resource::<Score>();
+        let write_score = SystemAccess::new().write_resource::<Score>();
+        let read_pos_write_score =
+            SystemAccess::of::<ReadonlyAccess<Position>>().write_resource::<Score>();
+
+        assert!(!read_score.is_conflict_with(&read_score));
+        assert!(read_score.is_conflict_with(&write_score));
+        assert!(write_score.is_conflict_with(&read_score));
+        assert!(read_pos_write_score.is_conflict_with(&write_score));
+        assert!(!read_pos_write_score
+            .is_conflict_with(&SystemAccess::of::<ReadonlyAccess<Position>>()));
+    }
+
+    #[test]
+    fn resource_writers_run_in_separate_stages() {
+        let mut store = Store::new();
+        store.insert_resource(Score { value: 0 });
+
+        let mut schedule = Schedule::new();
+        for i in 1..=3 {
+            schedule.add_system_with_access(
+                SystemAccess::new().write_resource::<Score>(),
+                move |context| {
+                    let mut score = context.resource_mut::<Score>().unwrap();
+                    score.value = score.value * 10 + i;
+                },
+            );
+        }
+        schedule.add_system_with_access(
+            SystemAccess::new().read_resource::<Score>(),
+            |context| assert_eq!(context.resource::<Score>().unwrap().value, 123),
+        );
+
+        assert_eq!(schedule.stages().len(), 4);
+        schedule.run(&mut store);
+
+        assert_eq!(store.resource::<Score>().unwrap().value, 123);
+    }
+
+    #[test]
+    #[should_panic(expected = "Resource")]
+    fn undeclared_resource_write_panics() {
+        let mut store = Store::new();
+        store.insert_resource(Score { value: 0 });
+
+        let mut schedule = Schedule::new();
+        schedule.add_system_with_access(
+            SystemAccess::new().read_resource::<Score>(),
+            |context| {
+                context.resource_mut::<Score>();
+            },
+        );
+
+        schedule.run(&mut store);
+    }
+
+    #[test]
+    fn non_conflicting_systems_share_stage() {
+        let mut schedule = Schedule::new();
+        let read0 = schedule.add_system::<ReadonlyAccess<Position>>(|_| {});
+        let read1 = schedule.add_system::<ReadonlyAccess<(Position, Velocity)>>(|_| {});
+        let write = schedule.add_system::<WriteAccess<Position>>(|_| {});
+        let other = schedule.add_system::<WriteAccess<Health>>(|_| {});
+
+        assert_eq!(schedule.stages(), &[vec![read0, read1, other], vec![write]]);
+    }
+
+    #[test]
+    fn explicit_orders_change_stages() {
+        let mut schedule = Schedule::new();
+        let write = schedule.add_system::<WriteAccess<Position>>(|_| {});
+        let read = schedule.add_system::<ReadonlyAccess<Position>>(|_| {});
+        let health = schedule.add_system::<WriteAccess<Health>>(|_| {});
+
+        schedule.run_before(read, write);
+        schedule.run_after(health, write);
+
+        assert_eq!(schedule.stages(), &[vec![read], vec![write], vec![health]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cyclic_orders_panic() {
+        let mut schedule = Schedule::new();
+        let a = schedule.add_system::<WriteAccess<Position>>(|_| {});
+        let b = schedule.add_system::<WriteAccess<Health>>(|_| {});
+
+        schedule.run_before(a, b);
+        schedule.run_before(b, a);
+        schedule.stages();
+    }
+
+    #[test]
+    fn run_executes_systems_in_order() {
+        let mut store = Store::new();
+        for i in 0..100 {
+            let e = store.create_entity();
+            store.add_components(
+                e,
+                (
+                    Position { x: 0. },
+                    Velocity { x: i as f32 },
+                    Health { value: 0 },
+                ),
+            );
+        }
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut schedule = Schedule::new();
+
+        let move_log = log.clone();
+        let mut move_query = query::component::read_write::<Velocity, Position>();
+        schedule.add_system::<ReadWriteAccess<Velocity, Position>>(move |context| {
+            for (vel, pos) in context.component_query_iter(&mut move_query) {
+                pos.x += vel.x;
+            }
+            move_log.lock().unwrap().push("move");
+        });
+
+        let health_log = log.clone();
+        let mut health_query = query::component::write::<Health>();
+        schedule.add_system::<WriteAccess<Health>>(move |context| {
+            for health in context.component_query_iter(&mut health_query) {
+                health.value += 1;
+            }
+            health_log.lock().unwrap().push("health");
+        });
+
+        let check_log = log.clone();
+        let mut check_query = query::component::readonly::<(Position, Velocity)>();
+        schedule.add_system::<ReadonlyAccess<(Position, Velocity)>>(move |context| {
+            for (pos, vel) in context.component_query_iter(&mut check_query) {
+                assert_eq!(pos.x, vel.x);
+            }
+            check_log.lock().unwrap().push("check");
+        });
+
+        schedule.run(&mut store);
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[2], "check");
+
+        let mut query = query::component::readonly::<Health>();
+        assert!(store.component_query_iter(&mut query).all(|h| h.value == 1));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Hit {
+        value: i32,
+    }
+
+    #[test]
+    fn systems_send_events_after_stage() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, (Position { x: 1. }, Health { value: 10 }));
+        store.add_events::<Hit>();
+
+        let mut schedule = Schedule::new();
+        let mut position_query = query::component::readonly::<Position>();
+        let position_system = schedule.add_system_with_access(
+            SystemAccess::of::<ReadonlyAccess<Position>>().read_events::<Hit>(),
+            move |context| {
+                for pos in context.component_query_iter(&mut position_query) {
+                    context.send_event(Hit {
+                        value: pos.x as i32,
+                    });
+                }
+                assert!(context.events::<Hit>().unwrap().is_empty());
+            },
+        );
+
+        let mut health_query = query::component::readonly::<Health>();
+        let health_system =
+            schedule.add_system::<ReadonlyAccess<Health>>(move |context| {
+                for health in context.component_query_iter(&mut health_query) {
+                    context.send_event(Hit {
+                        value: health.value,
+                    });
+                }
+            });
+
+        let read_hits = Arc::new(Mutex::new(Vec::new()));
+        let reader_hits = read_hits.clone();
+        let reader_system = schedule.add_system_with_access(
+            SystemAccess::new().read_events::<Hit>(),
+            move |context| {
+                let events = context.events::<Hit>().unwrap();
+                let mut reader = events.reader();
+                reader_hits
+                    .lock()
+                    .unwrap()
+                    .extend(events.read(&mut reader).map(|hit| hit.value));
+            },
+        );
+        schedule.run_after(reader_system, position_system);
+        schedule.run_after(reader_system, health_system);
+
+        assert_eq!(schedule.stages()[0].len(), 2);
+        schedule.run(&mut store);
+
+        assert_eq!(*read_hits.lock().unwrap(), vec![1, 10]);
+        assert_eq!(store.events::<Hit>().unwrap().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Events")]
+    fn undeclared_events_read_panics() {
+        let mut store = Store::new();
+        store.add_events::<Hit>();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system::<ReadonlyAccess<Position>>(|context| {
+            context.events::<Hit>();
+        });
+
+        schedule.run(&mut store);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't declared by the system")]
+    fn undeclared_query_access_panics() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Position { x: 0. });
+
+        let mut schedule = Schedule::new();
+        let mut query = query::component::write::<Position>();
+        schedule.add_system::<ReadonlyAccess<Position>>(move |context| {
+            for pos in context.component_query_iter(&mut query) {
+                pos.x += 1.;
+            }
+        });
+
+        schedule.run(&mut store);
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{query, Component, SerializationRegistry, Store, Tag};
+
+    #[derive(Component, Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(Component, Clone, Copy)]
+    struct Unregistered {
+        _value: u32,
+    }
+
+    #[derive(Tag)]
+    struct PlayerTag;
+
+    #[derive(Tag)]
+    struct DrawOrder;
+
+    fn registry() -> SerializationRegistry {
+        let mut registry = SerializationRegistry::new();
+        registry
+            .register_component::<Position>("position")
+            .register_component::<Name>("name")
+            .register_tag::<PlayerTag>("player")
+            .register_order_group::<DrawOrder>("draw_order");
+
+        registry
+    }
+
+    #[test]
+    fn store_roundtrip_keeps_entities_components_tags_and_orders() {
+        let registry = registry();
+        let mut store = Store::new();
+
+        let player = store.create_entity();
+        store.add_components(
+            player,
+            (
+                Position { x: 1., y: 2. },
+                Name {
+                    value: "player".to_string(),
+                },
+            ),
+        );
+        store.add_tag::<PlayerTag>(player);
+
+        let dead = store.create_entity();
+        store.add_components(dead, Position { x: 0., y: 0. });
+
+        let tree = store.create_entity();
+        store.add_components(tree, Position { x: 5., y: 6. });
+        let empty = store.create_entity();
+
+        store.destroy_entity(dead);
+        store.set_parent(empty, tree);
+        store.add_entity_order_by::<DrawOrder>(tree);
+        store.add_entity_order_by::<DrawOrder>(player);
+
+        let json = serde_json::to_string(&store.serializable(&registry)).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let mut loaded = Store::deserialize(&registry, &mut deserializer).unwrap();
+
+        assert!(loaded.is_alive(player));
+        assert!(loaded.is_alive(tree));
+        assert!(loaded.is_alive(empty));
+        assert!(!loaded.is_alive(dead));
+        assert!(loaded.get_entity_archetype(empty).is_none());
+
+        let (position, name) = loaded
+            .get_components_refs::<(Position, Name)>(player)
+            .unwrap();
+        assert_eq!(*position, Position { x: 1., y: 2. });
+        assert_eq!(name.value, "player");
+        assert_eq!(
+            *loaded.get_components_refs::<Position>(tree).unwrap(),
+            Position { x: 5., y: 6. }
+        );
+
+        assert_eq!(loaded.parent(empty), Some(tree));
+        assert!(loaded.has_tag::<PlayerTag>(player));
+        assert!(!loaded.has_tag::<PlayerTag>(tree));
+
+        assert_eq!(loaded.get_first_entity_ordered_by::<DrawOrder>(), Some(tree));
+        assert_eq!(
+            loaded.get_next_entity_ordered_by::<DrawOrder>(tree),
+            Some(player)
+        );
+
+        let mut query = query::component::readonly::<Position>();
+        assert_eq!(loaded.component_query_iter(&mut query).len(), 2);
+
+        let created = loaded.create_entity();
+        assert_eq!(created.id, dead.id);
+        assert_ne!(created.version, dead.version);
+    }
+
+    #[derive(Tag)]
+    #[tag(archetype)]
+    struct StoredTag;
+
+    #[test]
+    fn store_roundtrip_restores_archetype_tags() {
+        let mut registry = registry();
+        registry.register_tag::<StoredTag>("stored");
+        let mut store = Store::new();
+
+        let tagged = store.create_entity();
+        store.add_components(tagged, Position { x: 1., y: 2. });
+        store.add_tag::<StoredTag>(tagged);
+        let tag_only = store.create_entity();
+        store.add_tag::<StoredTag>(tag_only);
+
+        let json = serde_json::to_string(&store.serializable(&registry)).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let mut loaded = Store::deserialize(&registry, &mut deserializer).unwrap();
+
+        assert!(loaded.has_tag::<StoredTag>(tagged));
+        assert!(loaded.has_tag::<StoredTag>(tag_only));
+        assert!(loaded.get_components_refs::<StoredTag>(tag_only).is_some());
+
+        let mut query = query::component::readonly::<Position>().with::<StoredTag>();
+        let positions: Vec<_> = loaded.component_query_iter(&mut query).collect();
+        assert_eq!(positions, vec![&Position { x: 1., y: 2. }]);
+    }
+
+    #[test]
+    fn serialization_fails_for_unregistered_component() {
+        let registry = registry();
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, (Position { x: 0., y: 0. }, Unregistered { _value: 0 }));
+
+        let result = serde_json::to_string(&store.serializable(&registry));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialization_fails_for_unknown_component_name() {
+        let json = r#"{
+            "format_version": 1,
+            "entities": { "versions": [1], "alive": [0] },
+            "archetypes": [ { "components": ["velocity"], "entities": [[0, 1.0]] } ],
+            "tags": [],
+            "order_groups": []
+        }"#;
+
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let result = Store::deserialize(&registry(), &mut deserializer);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserialization_rolls_back_entity_with_invalid_component() {
+        let json = r#"{
+            "format_version": 1,
+            "entities": { "versions": [1], "alive": [0] },
+            "archetypes": [ {
+                "components": ["name", "position"],
+                "entities": [[0, { "value": "name" }, { "x": "invalid" }]]
+            } ],
+            "tags": [],
+            "order_groups": []
+        }"#;
+
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let result = Store::deserialize(&registry(), &mut deserializer);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    use crate::{Component, ComponentReadOnlyQuery, Entity, Store, Tag};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(Component, Clone)]
+    struct Shared {
+        value: Rc<()>,
+    }
+
+    #[derive(Tag)]
+    struct Selected;
+
+    #[derive(Tag)]
+    struct RenderOrder;
+
+    fn spawn(store: &mut Store, x: f32, name: &str) -> Entity {
+        let e = store.create_entity();
+        store.add_components(
+            e,
+            (
+                Position { x, y: 0. },
+                Name {
+                    value: String::from(name),
+                },
+            ),
+        );
+
+        e
+    }
+
+    #[test]
+    fn clone_entity_copies_components_and_tags() {
+        let mut store = Store::new();
+        store.register_clone::<Name>();
+        let e = spawn(&mut store, 1., "Player");
+        store.add_tag::<Selected>(e);
+
+        let clone = store.clone_entity(e);
+        store
+            .get_components_refs_mut::<Name>(e)
+            .unwrap()
+            .value
+            .push('!');
+
+        assert_ne!(clone, e);
+        assert!(store.has_tag::<Selected>(clone));
+        assert_eq!(
+            store.get_components_refs::<(Position, Name)>(clone),
+            Some((
+                &Position { x: 1., y: 0. },
+                &Name {
+                    value: String::from("Player")
+                }
+            ))
+        );
+        assert_eq!(
+            store.get_components_refs::<Name>(e).unwrap().value,
+            "Player!"
+        );
+    }
+
+    #[test]
+    fn clone_entity_fills_new_pages() {
+        let mut store = Store::new();
+        store.register_clone::<Shared>();
+        let value = Rc::new(());
+        let e = store.create_entity();
+        store.add_components(
+            e,
+            Shared {
+                value: value.clone(),
+            },
+        );
+
+        let clones: Vec<_> = (0..1000).map(|_| store.clone_entity(e)).collect();
+        assert_eq!(Rc::strong_count(&value), 1002);
+
+        let shared = store.get_components_refs::<Shared>(clones[0]).unwrap();
+        assert!(Rc::ptr_eq(&shared.value, &value));
+
+        for clone in clones {
+            store.destroy_entity(clone);
+        }
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(store);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn clone_entity_without_components_copies_tags() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_tag::<Selected>(e);
+
+        let clone = store.clone_entity(e);
+
+        assert!(store.has_tag::<Selected>(clone));
+        assert!(store.get_entity_archetype(clone).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be cloned without Store::register_clone")]
+    fn clone_entity_with_unregistered_drop_component_panics() {
+        let mut store = Store::new();
+        let e = spawn(&mut store, 1., "Player");
+
+        store.clone_entity(e);
+    }
+
+    #[derive(Component)]
+    struct Exploding {
+        _value: Box<u32>,
+    }
+
+    impl Clone for Exploding {
+        fn clone(&self) -> Self {
+            panic!("Exploding clone")
+        }
+    }
+
+    #[test]
+    fn clone_entity_panic_rolls_back_the_clone() {
+        let mut store = Store::new();
+        store.register_clone::<Shared>();
+        store.register_clone::<Exploding>();
+
+        let value = Rc::new(());
+        let e = store.create_entity();
+        store.add_components(
+            e,
+            (
+                Shared {
+                    value: value.clone(),
+                },
+                Exploding {
+                    _value: Box::new(1),
+                },
+            ),
+        );
+
+        let result = catch_unwind(AssertUnwindSafe(|| store.clone_entity(e)));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        let mut query = ComponentReadOnlyQuery::<Shared>::new();
+        assert_eq!(store.component_query_iter(&mut query).count(), 1);
+        assert!(store.is_alive(e));
+
+        let next = store.create_entity();
+        assert_eq!(next.id, e.id + 1);
+
+        drop(store);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn clone_entity_with_order_groups_follows_entity() {
+        let mut store = Store::new();
+        let e0 = store.create_entity();
+        let e1 = store.create_entity();
+        store.add_entity_order_by::<RenderOrder>(e0);
+        store.add_entity_order_by::<RenderOrder>(e1);
+
+        let unordered = store.clone_entity(e0);
+        let clone = store.clone_entity_with_order_groups(e0);
+
+        assert_eq!(
+            store.get_next_entity_ordered_by::<RenderOrder>(e0),
+            Some(clone)
+        );
+        assert_eq!(
+            store.get_next_entity_ordered_by::<RenderOrder>(clone),
+            Some(e1)
+        );
+        assert_eq!(
+            store.get_next_entity_ordered_by::<RenderOrder>(unordered),
+            None
+        );
+        assert_eq!(
+            store.get_previous_entity_ordered_by::<RenderOrder>(unordered),
+            None
+        );
+    }
+
+    #[test]
+    fn clone_entity_calls_add_hooks() {
+        let mut store = Store::new();
+        store.register_clone::<Name>();
+        let added = Rc::new(RefCell::new(Vec::new()));
+        let log = added.clone();
+        store.on_add::<Name>(move |e, name| {
+            log.borrow_mut().push((e, name.value.clone()))
+        });
+        let log = added.clone();
+        store
+            .on_tag_added::<Selected>(move |e| log.borrow_mut().push((e, String::new())));
+
+        let e = spawn(&mut store, 1., "Player");
+        store.add_tag::<Selected>(e);
+        added.borrow_mut().clear();
+        let clone = store.clone_entity(e);
+
+        assert_eq!(
+            *added.borrow(),
+            vec![(clone, String::from("Player")), (clone, String::new())]
+        );
+    }
+
+    #[test]
+    fn store_clone_is_independent_snapshot() {
+        let mut store = Store::new();
+        store.register_clone::<Name>();
+        let entities: Vec<_> = (0..20)
+            .map(|i| spawn(&mut store, i as f32, &i.to_string()))
+            .collect();
+        store.destroy_entity(entities[3]);
+        store.add_tag::<Selected>(entities[5]);
+        store.set_parent(entities[6], entities[5]);
+        store.add_entity_order_by::<RenderOrder>(entities[7]);
+
+        let mut snapshot = store.clone();
+        store
+            .get_components_refs_mut::<Position>(entities[0])
+            .unwrap()
+            .x = 100.;
+        store.destroy_entity(entities[1]);
+
+        assert!(snapshot.is_alive(entities[1]));
+        assert!(!snapshot.is_alive(entities[3]));
+        assert!(snapshot.has_tag::<Selected>(entities[5]));
+        assert_eq!(snapshot.parent(entities[6]), Some(entities[5]));
+        assert_eq!(
+            snapshot.get_first_entity_ordered_by::<RenderOrder>(),
+            Some(entities[7])
+        );
+
+        let mut query = ComponentReadOnlyQuery::<(Position, Name)>::new();
+        let mut snapshot_items: Vec<_> = snapshot
+            .component_query_iter(&mut query)
+            .map(|(pos, name)| (pos.x, name.value.clone()))
+            .collect();
+        snapshot_items.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let expected: Vec<_> = (0..20)
+            .filter(|&i| i != 3)
+            .map(|i| (i as f32, i.to_string()))
+            .collect();
+        assert_eq!(snapshot_items, expected);
+
+        let e = snapshot.create_entity();
+        assert_eq!(e.id, entities[3].id);
+        assert_ne!(e, entities[3]);
+    }
+}
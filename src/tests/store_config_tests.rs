@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::{Archetype, Component, Store, StoreConfig};
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Small {
+        value: u8,
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Large {
+        value: [u64; 16],
+    }
+
+    #[test]
+    fn store_with_small_page_size_keeps_values() {
+        let mut store = Store::with_config(StoreConfig::new().page_size(256));
+        let entities = store.spawn_batch((0..100).map(|i| {
+            (
+                Small { value: i as u8 },
+                Large {
+                    value: [i as u64; 16],
+                },
+            )
+        }));
+
+        for (i, &e) in entities.iter().enumerate() {
+            let (small, large) = store.get_components_refs::<(Small, Large)>(e).unwrap();
+            assert_eq!(small.value, i as u8);
+            assert_eq!(large.value, [i as u64; 16]);
+        }
+    }
+
+    #[test]
+    fn archetype_page_size_override_is_used_only_for_archetype() {
+        let store = Store::with_config(
+            StoreConfig::new()
+                .page_size(1024)
+                .archetype_page_size::<Large>(64 * 1024),
+        );
+
+        assert_eq!(
+            store.archetype_page_size(&Archetype::with_components::<Large>()),
+            64 * 1024
+        );
+        assert_eq!(
+            store.archetype_page_size(&Archetype::with_components::<(Small, Large)>()),
+            1024
+        );
+    }
+
+    #[test]
+    fn pages_with_different_sizes_are_reused_between_archetypes() {
+        let mut store = Store::with_config(
+            StoreConfig::new()
+                .page_size(128)
+                .archetype_page_size::<Large>(64 * 1024),
+        );
+
+        let smalls = store.spawn_batch((0..64).map(|i| Small { value: i as u8 }));
+        for e in smalls {
+            store.destroy_entity(e);
+        }
+
+        let larges = store.spawn_batch((0..200).map(|i| Large {
+            value: [i as u64; 16],
+        }));
+        let smalls = store.spawn_batch((0..64).map(|i| Small { value: i as u8 }));
+
+        for (i, &e) in larges.iter().enumerate() {
+            let large = store.get_components_refs::<Large>(e).unwrap();
+            assert_eq!(large.value, [i as u64; 16]);
+        }
+        for (i, &e) in smalls.iter().enumerate() {
+            let small = store.get_components_refs::<Small>(e).unwrap();
+            assert_eq!(small.value, i as u8);
+        }
+    }
+
+    #[test]
+    fn component_larger_than_page_gets_dedicated_page() {
+        let mut store = Store::with_config(StoreConfig::new().page_size(64));
+        let entities = store.spawn_batch((0..10).map(|i| Large {
+            value: [i as u64; 16],
+        }));
+
+        store.add_components(entities[3], Small { value: 3 });
+        store.destroy_entity(entities[0]);
+
+        for (i, &e) in entities.iter().enumerate().skip(1) {
+            let large = store.get_components_refs::<Large>(e).unwrap();
+            assert_eq!(large.value, [i as u64; 16]);
+        }
+        assert_eq!(
+            store
+                .get_components_refs::<Small>(entities[3])
+                .unwrap()
+                .value,
+            3
+        );
+    }
+}
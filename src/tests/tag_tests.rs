@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::{Store, Tag};
+    use crate::{Component, ComponentReadOnlyQuery, Entity, Store, Tag};
 
     #[derive(Tag)]
     struct TestTag;
@@ -8,6 +8,9 @@ mod tests {
     #[derive(Tag)]
     struct OtherTestTag;
 
+    #[derive(Tag)]
+    struct NeverAddedTag;
+
     #[test]
     fn add_remove_tag_as_expected() {
         let mut store = Store::new();
@@ -43,4 +46,332 @@ mod tests {
         assert!(store.has_tag::<TestTag>(e) == false);
         assert!(store.has_tag::<OtherTestTag>(e) == false);
     }
-}
\ No newline at end of file
+
+    #[derive(Component)]
+    struct ZeroSizedComponent;
+
+    #[derive(Tag)]
+    #[tag(archetype)]
+    struct StoredTag;
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Health {
+        value: u32,
+    }
+
+    #[test]
+    fn zero_sized_component_is_stored_in_archetype() {
+        let mut store = Store::new();
+        let entities: Vec<_> = (0..3000)
+            .map(|i| {
+                let e = store.create_entity();
+                store.add_components(e, Health { value: i });
+                if i % 3 == 0 {
+                    store.add_components(e, ZeroSizedComponent);
+                }
+                e
+            })
+            .collect();
+
+        let mut query =
+            ComponentReadOnlyQuery::<Health>::new().with::<ZeroSizedComponent>();
+        let tagged: Vec<_> = store
+            .component_query_iter(&mut query)
+            .with_entities()
+            .map(|(e, h)| (e, h.value))
+            .collect();
+
+        assert_eq!(tagged.len(), 1000);
+        for (e, value) in tagged {
+            assert_eq!(entities[value as usize], e);
+            assert_eq!(value % 3, 0);
+        }
+
+        store.remove_components::<ZeroSizedComponent>(entities[0]);
+        assert!(store
+            .get_components_refs::<ZeroSizedComponent>(entities[0])
+            .is_none());
+        assert_eq!(
+            store
+                .get_components_refs::<Health>(entities[0])
+                .unwrap()
+                .value,
+            0
+        );
+    }
+
+    #[test]
+    fn archetype_with_only_zero_sized_components_as_expected() {
+        let mut store = Store::new();
+        let entities = store.spawn_batch((0..10_000).map(|_| ZeroSizedComponent));
+
+        assert!(entities
+            .iter()
+            .all(|&e| store.get_components_refs::<ZeroSizedComponent>(e).is_some()));
+    }
+
+    fn spawn_with_stored_tag_on_even(store: &mut Store, count: u32) -> Vec<Entity> {
+        (0..count)
+            .map(|i| {
+                let e = store.create_entity();
+                store.add_components(e, Health { value: i });
+                if i % 2 == 0 {
+                    store.add_tag::<StoredTag>(e);
+                }
+                e
+            })
+            .collect()
+    }
+
+    #[test]
+    fn archetype_tag_is_matched_as_component() {
+        let mut store = Store::new();
+        let entities = spawn_with_stored_tag_on_even(&mut store, 100);
+
+        assert!(store.has_tag::<StoredTag>(entities[0]));
+        assert!(store
+            .get_entity_archetype(entities[0])
+            .unwrap()
+            .has_component::<StoredTag>());
+        assert!(!store
+            .get_entity_archetype(entities[1])
+            .unwrap()
+            .has_component::<StoredTag>());
+
+        let mut query = ComponentReadOnlyQuery::<Health>::new().with::<StoredTag>();
+        let mut values: Vec<_> = store
+            .component_query_iter(&mut query)
+            .map(|h| h.value)
+            .collect();
+        values.sort();
+        assert_eq!(values, (0..100).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn filter_tag_over_archetype_tag_as_expected() {
+        let mut store = Store::new();
+        spawn_with_stored_tag_on_even(&mut store, 100);
+
+        let mut query = ComponentReadOnlyQuery::<Health>::new();
+        let mut values: Vec<_> = store
+            .component_query_iter(&mut query)
+            .with_entities()
+            .filter_tag::<StoredTag>()
+            .map(|(_, h)| h.value)
+            .collect();
+        values.sort();
+        assert_eq!(values, (0..100).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn removing_archetype_tag_moves_entity_out_of_archetype() {
+        let mut store = Store::new();
+        let entities = spawn_with_stored_tag_on_even(&mut store, 10);
+
+        store.remove_tag::<StoredTag>(entities[0]);
+
+        assert!(!store.has_tag::<StoredTag>(entities[0]));
+        let archetype = store.get_entity_archetype(entities[0]).unwrap();
+        assert!(!archetype.has_component::<StoredTag>());
+        assert!(archetype.is_same_as(store.get_entity_archetype(entities[1]).unwrap()));
+        assert_eq!(
+            store.get_components_refs::<Health>(entities[0]),
+            Some(&Health { value: 0 })
+        );
+
+        let mut query = ComponentReadOnlyQuery::<Health>::new().with::<StoredTag>();
+        assert_eq!(store.component_query_iter(&mut query).count(), 4);
+    }
+
+    #[test]
+    fn entity_with_only_archetype_tag_as_expected() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_tag::<StoredTag>(e);
+        store.add_tag::<StoredTag>(e);
+
+        let archetype = store.get_entity_archetype(e).unwrap();
+        assert_eq!(archetype.component_count(), 1);
+        assert!(archetype.has_component::<StoredTag>());
+        assert!(store.get_components_refs::<StoredTag>(e).is_some());
+
+        store.remove_tag::<StoredTag>(e);
+        assert!(store.is_alive(e));
+        assert!(!store.has_tag::<StoredTag>(e));
+        assert!(store.get_entity_archetype(e).is_none());
+    }
+
+    #[test]
+    fn add_tag_to_dead_entity_does_nothing() {
+        let mut store = Store::new();
+        let dead = store.create_entity();
+        store.destroy_entity(dead);
+
+        store.add_tag::<StoredTag>(dead);
+        store.add_tag::<TestTag>(dead);
+        let reused = store.create_entity();
+
+        assert_eq!(reused.id, dead.id);
+        assert!(!store.has_tag::<StoredTag>(reused));
+        assert!(!store.has_tag::<TestTag>(reused));
+        assert!(store.get_entity_archetype(reused).is_none());
+    }
+
+    fn query_tagged_values<'a>(
+        store: &'a Store,
+        query: &'a mut ComponentReadOnlyQuery<Health>,
+        filter: impl FnOnce(
+            crate::query::component::WithEntitiesIter<'a, crate::ReadonlyAccess<Health>>,
+        ) -> Vec<u32>,
+    ) -> Vec<u32> {
+        let mut values = filter(store.component_query_iter(query).with_entities());
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn multiple_tag_filters_as_expected() {
+        let mut store = Store::new();
+        for i in 0..100 {
+            let e = store.create_entity();
+            store.add_components(e, Health { value: i });
+            if i % 2 == 0 {
+                store.add_tag::<TestTag>(e);
+            }
+            if i % 3 == 0 {
+                store.add_tag::<OtherTestTag>(e);
+            }
+        }
+
+        let mut query = ComponentReadOnlyQuery::<Health>::new();
+        let all_tags = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_tags::<(TestTag, OtherTestTag)>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        assert_eq!(all_tags, (0..100).step_by(6).collect::<Vec<_>>());
+
+        let any_tag = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_any_tag::<(TestTag, OtherTestTag)>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        let expected: Vec<_> = (0..100).filter(|i| i % 2 == 0 || i % 3 == 0).collect();
+        assert_eq!(any_tag, expected);
+
+        let excluded = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_tag::<TestTag>()
+                .exclude_tag::<OtherTestTag>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        let expected: Vec<_> = (0..100).filter(|i| i % 2 == 0 && i % 3 != 0).collect();
+        assert_eq!(excluded, expected);
+
+        let single = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_tag::<TestTag>().map(|(_, h)| h.value).collect()
+        });
+        assert_eq!(single, (0..100).step_by(2).collect::<Vec<_>>());
+
+        let single_excluded = query_tagged_values(&store, &mut query, |iter| {
+            iter.exclude_tag::<OtherTestTag>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        let expected: Vec<_> = (0..100).filter(|i| i % 3 != 0).collect();
+        assert_eq!(single_excluded, expected);
+    }
+
+    #[test]
+    fn missing_tag_filters_no_entities() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_components(e, Health { value: 1 });
+        store.add_tag::<TestTag>(e);
+
+        let mut query = ComponentReadOnlyQuery::<Health>::new();
+        let filtered = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_tag::<NeverAddedTag>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        assert!(filtered.is_empty());
+
+        let any = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_any_tag::<(NeverAddedTag, TestTag)>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        assert_eq!(any, vec![1]);
+
+        let excluded = query_tagged_values(&store, &mut query, |iter| {
+            iter.exclude_tag::<NeverAddedTag>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        assert_eq!(excluded, vec![1]);
+
+        let combined = query_tagged_values(&store, &mut query, |iter| {
+            iter.filter_tag::<NeverAddedTag>()
+                .exclude_tag::<OtherTestTag>()
+                .map(|(_, h)| h.value)
+                .collect()
+        });
+        assert!(combined.is_empty());
+    }
+
+    #[test]
+    fn tag_query_iter_yields_alive_tagged_entities() {
+        let mut store = Store::new();
+        let entities: Vec<_> = (0..200).map(|_| store.create_entity()).collect();
+        for e in entities.iter().step_by(3) {
+            store.add_tag::<TestTag>(*e);
+        }
+        store.add_components(entities[3], Health { value: 3 });
+        store.destroy_entity(entities[6]);
+
+        let tagged: Vec<_> = store.tag_query_iter::<TestTag>().collect();
+        let expected: Vec<_> = entities
+            .iter()
+            .copied()
+            .step_by(3)
+            .filter(|&e| e != entities[6])
+            .collect();
+
+        assert_eq!(tagged, expected);
+        assert_eq!(store.count_tagged::<TestTag>(), expected.len());
+        assert!(tagged.iter().all(|&e| store.is_alive(e)));
+    }
+
+    #[test]
+    fn tag_query_iter_skips_destroyed_entities() {
+        let mut store = Store::new();
+        let first = store.create_entity();
+        let last = store.create_entity();
+        store.add_tag::<TestTag>(first);
+        store.add_tag::<TestTag>(last);
+
+        store.destroy_entity(last);
+        assert_eq!(
+            store.tag_query_iter::<TestTag>().collect::<Vec<_>>(),
+            vec![first]
+        );
+
+        store.destroy_entity(first);
+        let reused = store.create_entity();
+        assert_eq!(reused.id, first.id);
+        assert_eq!(store.tag_query_iter::<TestTag>().count(), 0);
+        assert_eq!(store.count_tagged::<TestTag>(), 0);
+    }
+
+    #[test]
+    fn tag_query_iter_is_empty_for_missing_tag() {
+        let mut store = Store::new();
+        let e = store.create_entity();
+        store.add_tag::<TestTag>(e);
+
+        assert_eq!(store.tag_query_iter::<NeverAddedTag>().count(), 0);
+        assert_eq!(store.count_tagged::<NeverAddedTag>(), 0);
+        assert_eq!(store.count_tagged::<OtherTestTag>(), 0);
+    }
+}
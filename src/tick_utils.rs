@@ -0,0 +1,17 @@
+/// Whether the `tick` is after the `last_run_tick`, both are not after the `this_run_tick`.
+/// Ticks are `u32` and wrap, so they are compared by the distance back from the current
+/// tick instead of the values
+#[inline(always)]
+pub fn is_newer(tick: u32, last_run_tick: u32, this_run_tick: u32) -> bool {
+    this_run_tick.wrapping_sub(tick) < this_run_tick.wrapping_sub(last_run_tick)
+}
+
+/// The later of two ticks, both are not after the `this_tick`
+#[inline(always)]
+pub fn latest(lhs: u32, rhs: u32, this_tick: u32) -> u32 {
+    if is_newer(lhs, rhs, this_tick) {
+        lhs
+    } else {
+        rhs
+    }
+}